@@ -0,0 +1,50 @@
+//! Minimal timing harness for benchmarks,
+//! so they build with no extra dependencies.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+    time::Instant,
+};
+
+const ITERATIONS: u32 = 1_000_000;
+
+/// Runs `f` repeatedly and prints average time per iteration.
+///
+/// `cargo bench` passes `--bench` argument.
+/// Without it, e.g. under `cargo test --all-targets`,
+/// `f` runs once to check that the benchmark works.
+pub fn bench(name: &str, mut f: impl FnMut()) {
+    if !std::env::args().any(|arg| arg == "--bench") {
+        f();
+        return;
+    }
+
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let nanos = start.elapsed().as_nanos() as f64 / f64::from(ITERATIONS);
+    println!("{:<40} {:>10.2} ns/iter", name, nanos);
+}
+
+/// Polls the future once with no-op waker
+/// and returns its output.
+///
+/// # Panics
+///
+/// Panics if the future is pending.
+pub fn poll_ready<F>(future: Pin<&mut F>) -> F::Output
+where
+    F: Future + ?Sized,
+{
+    match future.poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future is pending"),
+    }
+}
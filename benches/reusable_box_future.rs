@@ -0,0 +1,31 @@
+//! Compares replacing future in `ReusableBoxFuture`
+//! with boxing fresh future on every tick.
+//!
+//! Run with `cargo bench --bench reusable_box_future`.
+
+mod common;
+
+use {
+    common::{bench, poll_ready},
+    maybe_sync::{future::ReusableBoxFuture, BoxFuture},
+    std::{hint::black_box, pin::Pin},
+};
+
+async fn tick(n: u64) -> u64 {
+    n + 1
+}
+
+fn main() {
+    let mut n = 0;
+    bench("tick/Box::pin", || {
+        let mut future: BoxFuture<'_, u64> = black_box(Box::pin(tick(n)));
+        n = poll_ready(future.as_mut());
+    });
+
+    let mut n = 0;
+    let mut future = ReusableBoxFuture::new(tick(n));
+    bench("tick/ReusableBoxFuture::set", || {
+        future.set(tick(n));
+        n = poll_ready(Pin::new(black_box(&mut future)));
+    });
+}
@@ -0,0 +1,37 @@
+//! Compares `boxed_small` with `Box::pin` for small futures
+//! that are ready on first poll.
+//!
+//! Run with `cargo bench --bench small_future`.
+
+mod common;
+
+use {
+    common::{bench, poll_ready},
+    maybe_sync::{BoxFuture, MaybeSendFutureExt},
+    std::{future::ready, hint::black_box, pin::pin},
+};
+
+fn main() {
+    // Futures go through `black_box` so the allocation is not optimized out.
+    bench("ready/Box::pin", || {
+        let mut future: BoxFuture<'_, u64> = black_box(Box::pin(ready(black_box(42))));
+        black_box(poll_ready(future.as_mut()));
+    });
+
+    bench("ready/boxed_small", || {
+        let future = black_box(ready(black_box(42u64)).boxed_small());
+        black_box(poll_ready(pin!(future)));
+    });
+
+    bench("async block/Box::pin", || {
+        let (a, b) = black_box((1u64, 2u64));
+        let mut future: BoxFuture<'_, u64> = black_box(Box::pin(async move { a + b }));
+        black_box(poll_ready(future.as_mut()));
+    });
+
+    bench("async block/boxed_small", || {
+        let (a, b) = black_box((1u64, 2u64));
+        let future = black_box(async move { a + b }.boxed_small());
+        black_box(poll_ready(pin!(future)));
+    });
+}
@@ -37,11 +37,50 @@ async def check(*, toolchain='stable', target=None, features=[], mandatory_featu
             raise Exception(f'`cargo {" ".join(args)}` failed\n{proc.stderr}')
 
 
+async def test(*, toolchain='stable', features=[]):
+    args = [f'+{toolchain}', 'test']
+    if len(features) > 0:
+        args.append(f'--features={",".join(features)}')
+
+    proc = await asyncio.create_subprocess_exec('cargo', *args, stderr=subprocess.PIPE)
+    returncode = await proc.wait()
+    if returncode != 0:
+        raise Exception(f'`cargo {" ".join(args)}` failed\n{proc.stderr}')
+
+
+async def miri(*, features=[], targets=[], filter=None):
+    args = ['+nightly', 'miri', 'test', *targets]
+    if len(features) > 0:
+        args.append(f'--features={",".join(features)}')
+
+    if filter is not None:
+        args.append(filter)
+
+    proc = await asyncio.create_subprocess_exec('cargo', *args, stderr=subprocess.PIPE)
+    returncode = await proc.wait()
+    if returncode != 0:
+        raise Exception(f'`cargo {" ".join(args)}` failed\n{proc.stderr}')
+
+
 features = [
     "sync",
-    "alloc"
+    "alloc",
+    "std",
+    "futures-io",
+    "futures",
+    "strict",
+    "rayon",
+    "tokio",
+    "gloo-timers",
+    "portable-atomic",
+    "crossbeam",
+    "serde",
+    "macros"
 ]
 
+# Keep in sync with `rust-version` in Cargo.toml.
+msrv = "1.85"
+
 
 async def run():
     await asyncio.gather(
@@ -51,6 +90,18 @@ async def run():
               features=features),
         check(toolchain="stable", target="wasm32-unknown-unknown",
               features=features),
+        check(toolchain=msrv, features=features),
+        test(features=["std", "strict"]),
+        test(toolchain=msrv, features=["std"]),
+        test(toolchain=msrv, features=["sync", "futures", "macros"]),
+        # Unsafe allocation reuse in `ReusableBoxFuture`.
+        miri(features=["std"], targets=["--doc"], filter="ReusableBoxFuture"),
+        miri(features=["std", "sync"], targets=["--doc"],
+             filter="ReusableBoxFuture"),
+        miri(features=["std"], targets=["--bench", "reusable_box_future"]),
+        miri(features=["std", "sync"],
+             targets=["--bench", "reusable_box_future"]),
+        test(features=["sync", "crossbeam", "futures"]),
     )
 
 
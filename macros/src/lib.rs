@@ -0,0 +1,1197 @@
+//! Procedural macros for [`maybe-sync`] crate.
+//!
+//! Use them through reexports in [`maybe-sync`] with "macros" feature.
+//!
+//! [`maybe-sync`]: https://docs.rs/maybe-sync
+
+use {
+    proc_macro::TokenStream,
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::{format_ident, quote, quote_spanned},
+    syn::{
+        parse::{Parse, ParseStream},
+        parse_macro_input, parse_quote,
+        punctuated::Punctuated,
+        spanned::Spanned,
+        visit_mut::VisitMut,
+        Attribute, Block, Data, DeriveInput, Field, FnArg, GenericParam, Generics, Ident, ImplItem,
+        Item, ItemFn, Lifetime, LifetimeParam, Pat, PatIdent, ReturnType, Signature, Token,
+        TraitItem, Type, TypeBareFn, TypeImplTrait, TypeParamBound, TypeReference, Visibility,
+        WherePredicate,
+    },
+};
+
+/// Turns `async fn` in traits and their impls into methods returning
+/// `maybe_sync::BoxFuture`, which is sendable only when "sync" feature
+/// of `maybe-sync` is enabled.
+///
+/// Bounds required for the future to be sendable are added
+/// only when "sync" feature is enabled:
+///
+/// * `Self: Sync` for `&self` methods and `Self: Send` for other receivers
+///   when "sync" feature is enabled, keeping the trait dyn compatible.
+/// * `T: `[`MaybeSend`] for type parameters and `impl Trait` arguments of the method.
+///
+/// All argument lifetimes, named or elided, outlive the returned future.
+///
+/// Expanded code refers to `::maybe_sync`, so the crate must not be renamed.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{dyn_maybe_sync, future::now_or_never, maybe_async_trait, BoxFuture, MaybeSend};
+/// #[maybe_async_trait]
+/// trait Storage {
+///     async fn load(&self, key: &str) -> Option<u32>;
+///
+///     async fn store(&mut self, key: &str, value: u32);
+///
+///     // Default method body.
+///     async fn load_or(&self, key: &str, default: u32) -> u32 {
+///         self.load(key).await.unwrap_or(default)
+///     }
+///
+///     // Generics on methods.
+///     // `Self: Sized` keeps the trait dyn compatible.
+///     async fn load_all<K>(&self, keys: K) -> Vec<u32>
+///     where
+///         K: IntoIterator<Item = &'static str>,
+///         K::IntoIter: MaybeSend,
+///         Self: Sized,
+///     {
+///         let mut values = Vec::new();
+///         for key in keys {
+///             values.extend(self.load(key).await);
+///         }
+///         values
+///     }
+/// }
+///
+/// struct Memory(Vec<(String, u32)>);
+///
+/// #[maybe_async_trait]
+/// impl Storage for Memory {
+///     async fn load(&self, key: &str) -> Option<u32> {
+///         self.0.iter().find(|(k, _)| k == key).map(|(_, v)| *v)
+///     }
+///
+///     async fn store(&mut self, key: &str, value: u32) {
+///         self.0.push((key.to_owned(), value));
+///     }
+/// }
+///
+/// let mut storage = Memory(Vec::new());
+/// now_or_never(storage.store("a", 1));
+/// now_or_never(storage.store("b", 2));
+///
+/// // Methods return `BoxFuture`.
+/// let future: BoxFuture<'_, Option<u32>> = storage.load("a");
+/// assert_eq!(now_or_never(future), Some(Some(1)));
+/// assert_eq!(now_or_never(storage.load_or("c", 3)), Some(3));
+/// assert_eq!(now_or_never(storage.load_all(["a", "b", "c"])), Some(vec![1, 2]));
+///
+/// // Trait stays dyn compatible.
+/// let storage: &dyn_maybe_sync!(Storage) = &storage;
+/// assert_eq!(now_or_never(storage.load("b")), Some(Some(2)));
+/// ```
+///
+/// Patterns, `mut` bindings, `impl Trait` arguments and `?` are supported.
+///
+/// ```
+/// # use {maybe_sync::{future::now_or_never, maybe_async_trait}, std::fmt::Display};
+/// #[maybe_async_trait]
+/// trait Parse {
+///     async fn parse(&self, (a, b): (&str, &str), sep: impl Display) -> Result<String, std::num::ParseIntError> {
+///         let mut sum: u32 = a.parse()?;
+///         sum += b.parse::<u32>()?;
+///         Ok(format!("{}{}{}", a, sep, sum))
+///     }
+///
+///     async fn count(mut items: Vec<u32>) -> usize {
+///         items.dedup();
+///         items.len()
+///     }
+/// }
+///
+/// struct Parser;
+///
+/// #[maybe_async_trait]
+/// impl Parse for Parser {}
+///
+/// assert_eq!(now_or_never(Parser.parse(("1", "2"), '=')), Some(Ok("1=3".to_owned())));
+/// assert!(now_or_never(Parser.parse(("1", "x"), '=')).unwrap().is_err());
+/// assert_eq!(now_or_never(Parser::count(vec![1, 1, 2])), Some(2));
+/// ```
+///
+/// Only traits and impl blocks are accepted.
+///
+/// ```compile_fail
+/// #[maybe_sync::maybe_async_trait]
+/// fn load(key: &str) -> Option<u32> {
+///     key.parse().ok()
+/// }
+/// ```
+///
+/// The attribute takes no arguments.
+///
+/// ```compile_fail
+/// #[maybe_sync::maybe_async_trait(send)]
+/// trait Storage {
+///     async fn load(&self, key: &str) -> Option<u32>;
+/// }
+/// ```
+///
+/// [`MaybeSend`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSend.html
+#[proc_macro_attribute]
+pub fn maybe_async_trait(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let args = TokenStream2::from(args);
+        return quote_spanned!(args.span() => compile_error!("`maybe_async_trait` takes no arguments");).into();
+    }
+
+    let mut item = parse_macro_input!(input as Item);
+    match &mut item {
+        Item::Trait(item) => {
+            for item in &mut item.items {
+                if let TraitItem::Fn(method) = item {
+                    if method.sig.asyncness.is_some() {
+                        let self_bounds =
+                            expand(&mut method.sig, method.default.as_mut(), Flavor::Trait);
+                        if let Some(self_bounds) = self_bounds {
+                            *item = TraitItem::Verbatim(split_by_mode(method, self_bounds));
+                        }
+                    }
+                }
+            }
+        }
+        Item::Impl(item) => {
+            for item in &mut item.items {
+                if let ImplItem::Fn(method) = item {
+                    if method.sig.asyncness.is_some() {
+                        let self_bounds =
+                            expand(&mut method.sig, Some(&mut method.block), Flavor::Trait);
+                        if let Some(self_bounds) = self_bounds {
+                            *item = ImplItem::Verbatim(split_by_mode(method, self_bounds));
+                        }
+                    }
+                }
+            }
+        }
+        item => {
+            return quote_spanned!(item.span() => compile_error!("`maybe_async_trait` expects trait or impl block");).into();
+        }
+    }
+    quote!(#item).into()
+}
+
+/// Bounds on `Self` for the future to capture the receiver.
+///
+/// `MaybeSend` and `MaybeSync` are not auto traits when "sync" feature is not enabled,
+/// and bounding `Self` with them would make the trait not dyn compatible.
+/// Instead method is emitted twice with `Send` or `Sync` bound and without it,
+/// and `maybe_sync` picks one according to its features.
+struct SelfBounds {
+    marker: Ident,
+    rest: TokenStream2,
+}
+
+fn split_by_mode<M>(method: &M, self_bounds: SelfBounds) -> TokenStream2
+where
+    M: Clone + HasSignature + quote::ToTokens,
+{
+    let SelfBounds { marker, rest } = self_bounds;
+    let marker = quote!(::core::marker::#marker);
+
+    let mut sync = method.clone();
+    sync.signature()
+        .generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: #marker + #rest));
+
+    let mut unsync = method.clone();
+    unsync
+        .signature()
+        .generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: #rest));
+
+    quote!(::maybe_sync::if_sync! { { #sync } else { #unsync } })
+}
+
+trait HasSignature {
+    fn signature(&mut self) -> &mut Signature;
+}
+
+impl HasSignature for syn::TraitItemFn {
+    fn signature(&mut self) -> &mut Signature {
+        &mut self.sig
+    }
+}
+
+impl HasSignature for syn::ImplItemFn {
+    fn signature(&mut self) -> &mut Signature {
+        &mut self.sig
+    }
+}
+
+const LIFETIME: &str = "'maybe_async";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Flavor {
+    /// `BoxFuture` returned from trait method.
+    Trait,
+    /// `BoxFuture` returned from free function or inherent method.
+    Boxed,
+    /// `LocalBoxFuture` returned from free function or inherent method.
+    Local,
+}
+
+fn expand(sig: &mut Signature, block: Option<&mut Block>, flavor: Flavor) -> Option<SelfBounds> {
+    let span = sig.asyncness.take().unwrap().span();
+    let future_lifetime = Lifetime::new(LIFETIME, span);
+    let local = flavor == Flavor::Local;
+
+    // Give names to elided lifetimes of arguments.
+    // Outside of traits elided lifetimes of references are replaced
+    // with the future lifetime, keeping it late-bound, so that functions
+    // can be coerced to higher-ranked function pointers.
+    let mut elided = ElidedLifetimes::default();
+    for arg in &mut sig.inputs {
+        match arg {
+            FnArg::Receiver(receiver) => {
+                if let Some((_, lifetime @ None)) = &mut receiver.reference {
+                    if flavor == Flavor::Trait {
+                        *lifetime = Some(elided.next(receiver.self_token.span));
+                    } else {
+                        *lifetime = Some(future_lifetime.clone());
+                    }
+                }
+            }
+            FnArg::Typed(arg) => match &mut *arg.ty {
+                Type::Reference(ty) if ty.lifetime.is_none() && flavor != Flavor::Trait => {
+                    ty.lifetime = Some(future_lifetime.clone());
+                    // Lifetimes behind the reference outlive it implicitly.
+                    elided.implied = true;
+                    elided.visit_type_mut(&mut ty.elem);
+                    elided.implied = false;
+                }
+                ty => elided.visit_type_mut(ty),
+            },
+        }
+    }
+
+    let mut predicates: Vec<TokenStream2> = Vec::new();
+    for param in &sig.generics.params {
+        match param {
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                predicates.push(quote!(#lifetime: #future_lifetime));
+            }
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                if local {
+                    predicates.push(quote!(#ident: #future_lifetime));
+                } else {
+                    predicates.push(quote!(#ident: ::maybe_sync::MaybeSend + #future_lifetime));
+                }
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+    for lifetime in &elided.bounded {
+        predicates.push(quote!(#lifetime: #future_lifetime));
+    }
+
+    let self_bounds = match sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) => {
+            let marker = match &receiver.reference {
+                Some(_) if receiver.mutability.is_none() => Ident::new("Sync", span),
+                _ => Ident::new("Send", span),
+            };
+            let rest = if receiver.reference.is_none() && receiver.colon_token.is_none() {
+                quote!(::core::marker::Sized + #future_lifetime)
+            } else {
+                quote!(#future_lifetime)
+            };
+            Some(SelfBounds { marker, rest })
+        }
+        _ => None,
+    };
+
+    // Anonymous type parameters are captured by the future as well.
+    for arg in &mut sig.inputs {
+        if let FnArg::Typed(arg) = arg {
+            if let Type::ImplTrait(TypeImplTrait { bounds, .. }) = &mut *arg.ty {
+                if !local {
+                    bounds.push(parse_quote!(::maybe_sync::MaybeSend));
+                }
+                bounds.push(parse_quote!(#future_lifetime));
+            }
+        }
+    }
+
+    let params = &mut sig.generics.params;
+    for (index, lifetime) in core::iter::once(future_lifetime.clone())
+        .chain(elided.lifetimes)
+        .enumerate()
+    {
+        params.insert(index, GenericParam::Lifetime(LifetimeParam::new(lifetime)));
+    }
+
+    let where_clause = sig.generics.make_where_clause();
+    for predicate in predicates {
+        where_clause.predicates.push(parse_quote!(#predicate));
+    }
+
+    let output = match &sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ty) => quote!(#ty),
+    };
+    sig.output = if local {
+        parse_quote!(-> ::maybe_sync::LocalBoxFuture<#future_lifetime, #output>)
+    } else {
+        parse_quote!(-> ::maybe_sync::BoxFuture<#future_lifetime, #output>)
+    };
+
+    if let Some(block) = block {
+        // Move every argument into the future, as `async fn` does.
+        let mut bindings = Vec::new();
+        for (index, arg) in sig.inputs.iter_mut().enumerate() {
+            if let FnArg::Typed(arg) = arg {
+                match &mut *arg.pat {
+                    Pat::Ident(PatIdent {
+                        ident,
+                        by_ref: None,
+                        mutability,
+                        subpat: None,
+                        ..
+                    }) => {
+                        let mutability = mutability.take();
+                        bindings.push(quote!(let #mutability #ident = #ident;));
+                    }
+                    pat => {
+                        let ident = format_ident!("__arg{}", index, span = pat.span());
+                        bindings.push(quote!(let #pat = #ident;));
+                        *pat = parse_quote!(#ident);
+                    }
+                }
+            }
+        }
+
+        let body = &*block;
+        *block = parse_quote!({
+            ::maybe_sync::__private::Box::pin(async move {
+                #(#bindings)*
+                #[allow(unreachable_code)]
+                let __ret: #output = #body;
+                #[allow(unreachable_code)]
+                __ret
+            })
+        });
+    }
+
+    self_bounds
+}
+
+/// Replaces elided lifetimes with named ones.
+#[derive(Default)]
+struct ElidedLifetimes {
+    lifetimes: Vec<Lifetime>,
+    /// Lifetimes that need explicit bound to outlive the future.
+    bounded: Vec<Lifetime>,
+    implied: bool,
+}
+
+impl ElidedLifetimes {
+    fn next(&mut self, span: Span) -> Lifetime {
+        let lifetime = Lifetime::new(&format!("'life{}", self.lifetimes.len()), span);
+        self.lifetimes.push(lifetime.clone());
+        if !self.implied {
+            self.bounded.push(lifetime.clone());
+        }
+        lifetime
+    }
+}
+
+impl VisitMut for ElidedLifetimes {
+    fn visit_type_reference_mut(&mut self, ty: &mut TypeReference) {
+        if ty.lifetime.is_none() {
+            ty.lifetime = Some(self.next(ty.and_token.span));
+        }
+        self.visit_type_mut(&mut ty.elem);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.next(lifetime.span());
+        }
+    }
+
+    // Lifetimes elided in function pointers and `Fn` traits belong to them.
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
+
+/// Turns `async fn` outside of traits into function returning
+/// `maybe_sync::BoxFuture`, which is sendable only when "sync" feature
+/// of `maybe-sync` is enabled.\
+/// With `local` argument `maybe_sync::LocalBoxFuture` is returned instead.
+///
+/// Works on free functions and inherent methods.
+/// Bounds required for the future to be sendable are added:
+///
+/// * `Self: `[`MaybeSync`] for `&self` methods and `Self: `[`MaybeSend`] for other receivers.
+/// * `T: `[`MaybeSend`] for type parameters and `impl Trait` arguments.
+///
+/// Elided lifetimes of references become lifetime of the returned future,
+/// so function can still be coerced into a higher-ranked function pointer.
+/// All other argument lifetimes outlive the returned future.
+///
+/// Expanded code refers to `::maybe_sync`, so the crate must not be renamed.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{boxed_future, future::now_or_never, BoxFuture, LocalBoxFuture}, std::task::{Context, Poll, Waker}};
+/// #[boxed_future]
+/// async fn len(text: &str) -> usize {
+///     text.len()
+/// }
+///
+/// #[boxed_future]
+/// async fn words(text: &str) -> usize {
+///     text.split_whitespace().count()
+/// }
+///
+/// // Table of function pointers.
+/// let handlers: [for<'a> fn(&'a str) -> BoxFuture<'a, usize>; 2] = [len, words];
+/// let results: Vec<_> = handlers
+///     .iter()
+///     .map(|handler| now_or_never(handler("a few words")).unwrap())
+///     .collect();
+/// assert_eq!(results, [11, 3]);
+///
+/// struct Cache {
+///     entries: Vec<(String, u32)>,
+/// }
+///
+/// impl Cache {
+///     #[boxed_future]
+///     pub async fn get(&self, key: &str) -> Option<u32> {
+///         self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| *v)
+///     }
+///
+///     #[boxed_future]
+///     pub async fn insert(&mut self, key: impl Into<String>, value: u32) {
+///         self.entries.push((key.into(), value));
+///     }
+/// }
+///
+/// let mut cache = Cache { entries: Vec::new() };
+/// now_or_never(cache.insert("a", 1));
+/// let future: BoxFuture<'_, Option<u32>> = cache.get("a");
+/// assert_eq!(now_or_never(future), Some(Some(1)));
+///
+/// // Main-thread-only functions may hold non-sendable values across awaits.
+/// #[boxed_future(local)]
+/// async fn shared_len(text: std::rc::Rc<str>) -> usize {
+///     let text = text.clone();
+///     async {}.await;
+///     text.len()
+/// }
+///
+/// let mut future: LocalBoxFuture<'static, usize> = shared_len("abc".into());
+/// let mut cx = Context::from_waker(Waker::noop());
+/// assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(3));
+/// ```
+///
+/// [`MaybeSend`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSend.html
+/// [`MaybeSync`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSync.html
+#[proc_macro_attribute]
+pub fn boxed_future(args: TokenStream, input: TokenStream) -> TokenStream {
+    let flavor = match parse_bounds_arg(args.into()) {
+        Ok(None) => Flavor::Boxed,
+        Ok(Some(arg)) if arg == "local" => Flavor::Local,
+        Ok(Some(arg)) => {
+            return quote_spanned!(arg.span() => compile_error!("expected `local`");).into();
+        }
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut item = parse_macro_input!(input as ItemFn);
+    if item.sig.asyncness.is_none() {
+        return quote_spanned!(item.sig.fn_token.span() => compile_error!("`boxed_future` expects `async fn`");).into();
+    }
+
+    let self_bounds = expand(&mut item.sig, Some(&mut item.block), flavor);
+    if let (Some(FnArg::Receiver(receiver)), Some(SelfBounds { marker, .. })) =
+        (item.sig.inputs.first(), self_bounds)
+    {
+        let mut bounds = Vec::new();
+        if flavor == Flavor::Boxed {
+            let marker = format_ident!("Maybe{}", marker);
+            bounds.push(quote!(::maybe_sync::#marker));
+        }
+        // Referenced `Self` outlives the future implicitly.
+        if receiver.reference.is_none() {
+            let future_lifetime = Lifetime::new(LIFETIME, receiver.self_token.span);
+            bounds.push(quote!(#future_lifetime));
+        }
+        if !bounds.is_empty() {
+            item.sig
+                .generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(Self: #(#bounds)+*));
+        }
+    }
+    quote!(#item).into()
+}
+
+/// Adds `MaybeSend` bounds to a trait, its type parameters
+/// and its associated types.\
+/// With `sync` argument `MaybeSync` bounds are added as well.
+///
+/// Type parameters and associated types marked with
+/// `#[maybe_send_bounds(skip)]` are left as is.
+///
+/// Bounds on the trait itself make trait objects sendable
+/// when "sync" feature is enabled, so `Box<dyn Trait>`
+/// needs no extra markers.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{maybe_send_bounds, send_ok, share_ok, BoxFuture, MaybeSend, MaybeSync, Rc};
+/// #[maybe_send_bounds(sync)]
+/// trait Loader<K> {
+///     type Asset: Clone;
+///
+///     // Handles never leave the thread that produced them.
+///     #[maybe_send_bounds(skip)]
+///     type Handle;
+///
+///     fn load(&self, key: K) -> BoxFuture<'_, Option<Self::Asset>>;
+///
+///     fn handle(&self, asset: &Self::Asset) -> Self::Handle;
+/// }
+///
+/// // Bounds on `Self` and associated types are implied wherever the trait is used.
+/// // Bounds on type parameters are not and must be repeated.
+/// fn check<L: Loader<K>, K: MaybeSend + MaybeSync>() {
+///     send_ok::<L>();
+///     share_ok::<L>();
+///     send_ok::<L::Asset>();
+///     share_ok::<L::Asset>();
+/// }
+///
+/// struct Strings(Vec<String>);
+///
+/// impl Loader<usize> for Strings {
+///     type Asset = String;
+///     type Handle = std::rc::Rc<String>;
+///
+///     fn load(&self, key: usize) -> BoxFuture<'_, Option<String>> {
+///         Box::pin(async move { self.0.get(key).cloned() })
+///     }
+///
+///     fn handle(&self, asset: &String) -> std::rc::Rc<String> {
+///         std::rc::Rc::new(asset.clone())
+///     }
+/// }
+///
+/// check::<Strings, usize>();
+///
+/// // Trait objects get the bounds too.
+/// let loader: Rc<dyn Loader<usize, Asset = String, Handle = std::rc::Rc<String>>> =
+///     Rc::new(Strings(vec!["a".to_owned()]));
+/// share_ok::<dyn Loader<usize, Asset = String, Handle = std::rc::Rc<String>>>();
+///
+/// let asset = maybe_sync::future::now_or_never(loader.load(0)).unwrap();
+/// assert_eq!(asset.as_deref(), Some("a"));
+/// assert_eq!(*loader.handle(&asset.unwrap()), "a");
+/// ```
+///
+/// Only traits are accepted.
+///
+/// ```compile_fail
+/// #[maybe_sync::maybe_send_bounds]
+/// struct Loader<K>(Vec<K>);
+/// ```
+///
+/// `sync` is the only argument of the attribute on trait,
+/// and `skip` is the only argument on its type parameters and associated types.
+///
+/// ```compile_fail
+/// #[maybe_sync::maybe_send_bounds(send)]
+/// trait Loader<K> {}
+/// ```
+///
+/// ```compile_fail
+/// #[maybe_sync::maybe_send_bounds]
+/// trait Loader<K> {
+///     #[maybe_send_bounds(skp)]
+///     type Handle;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn maybe_send_bounds(args: TokenStream, input: TokenStream) -> TokenStream {
+    let sync = match parse_bounds_arg(args.into()) {
+        Ok(None) => false,
+        Ok(Some(arg)) if arg == "sync" => true,
+        Ok(Some(arg)) => {
+            return quote_spanned!(arg.span() => compile_error!("expected `sync`");).into();
+        }
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut item = parse_macro_input!(input as Item);
+    let item = match &mut item {
+        Item::Trait(item) => item,
+        item => {
+            return quote_spanned!(item.span() => compile_error!("`maybe_send_bounds` expects trait");).into();
+        }
+    };
+
+    let bounds: Vec<TypeParamBound> = if sync {
+        vec![
+            parse_quote!(::maybe_sync::MaybeSend),
+            parse_quote!(::maybe_sync::MaybeSync),
+        ]
+    } else {
+        vec![parse_quote!(::maybe_sync::MaybeSend)]
+    };
+
+    let result = (|| {
+        for param in &mut item.generics.params {
+            if let GenericParam::Type(param) = param {
+                if !take_skip(&mut param.attrs)? {
+                    param.colon_token.get_or_insert_with(Default::default);
+                    param.bounds.extend(bounds.iter().cloned());
+                }
+            }
+        }
+
+        for trait_item in &mut item.items {
+            if let TraitItem::Type(ty) = trait_item {
+                if !take_skip(&mut ty.attrs)? {
+                    ty.colon_token.get_or_insert_with(Default::default);
+                    ty.bounds.extend(bounds.iter().cloned());
+                }
+            }
+        }
+
+        Ok::<_, syn::Error>(())
+    })();
+
+    if let Err(err) = result {
+        return err.to_compile_error().into();
+    }
+
+    item.colon_token.get_or_insert_with(Default::default);
+    item.supertraits.extend(bounds);
+
+    quote!(#item).into()
+}
+
+fn parse_bounds_arg(args: TokenStream2) -> syn::Result<Option<Ident>> {
+    if args.is_empty() {
+        return Ok(None);
+    }
+    syn::parse2(args).map(Some)
+}
+
+/// Removes `#[maybe_send_bounds(skip)]` attributes,
+/// returning `true` if there were any.
+fn take_skip(attrs: &mut Vec<Attribute>) -> syn::Result<bool> {
+    let mut skip = false;
+    let mut result = Ok(());
+    attrs.retain(|attr| {
+        if !attr.path().is_ident("maybe_send_bounds") {
+            return true;
+        }
+        match attr.parse_args::<Ident>() {
+            Ok(arg) if arg == "skip" => skip = true,
+            Ok(arg) => result = Err(syn::Error::new(arg.span(), "expected `skip`")),
+            Err(err) => result = Err(err),
+        }
+        false
+    });
+    result.map(|()| skip)
+}
+
+/// Appends [`MaybeSend`] and [`MaybeSync`] bounds
+/// to every predicate in where clauses of given items.
+///
+/// Prefix items with `send:` or `sync:` to append only
+/// [`MaybeSend`] or [`MaybeSync`] respectively.
+///
+/// Existing bounds, lifetimes and higher-ranked bounds are kept.
+/// Predicates on lifetimes are left untouched.\
+/// Only top-level where clauses are rewritten,
+/// where clauses of methods inside item bodies are not.
+///
+/// Expanded code refers to `::maybe_sync`, so the crate must not be renamed.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{maybe_bounds, send_ok, share_ok}, std::collections::HashMap};
+/// pub struct Registry<T, F> {
+///     items: HashMap<u32, T>,
+///     filter: F,
+/// }
+///
+/// maybe_bounds! {
+///     impl<'a, T, F> Registry<T, F>
+///     where
+///         T: Clone + Into<HashMap<u32, Vec<T>>> + 'static,
+///         F: for<'b> Fn(&'b T) -> bool,
+///         'a: 'static,
+///     {
+///         pub fn check(&self) {
+///             send_ok::<T>();
+///             share_ok::<T>();
+///             send_ok::<F>();
+///             share_ok::<F>();
+///         }
+///     }
+///
+///     pub trait Component<T>
+///     where
+///         T: Clone,
+///     {
+///         fn get(&self) -> T;
+///     }
+/// }
+///
+/// maybe_bounds! {
+///     send: fn spawn<F>(f: F) -> F::Output where F: FnOnce() -> u32 {
+///         send_ok::<F>();
+///         f()
+///     }
+/// }
+///
+/// assert_eq!(spawn(|| 42), 42);
+/// ```
+///
+/// Any number of items can be given in one invocation.
+///
+/// ```
+/// # use maybe_sync::{maybe_bounds, send_ok, share_ok};
+/// pub trait Component {}
+///
+/// impl Component for u32 {}
+///
+/// maybe_bounds! {
+///     pub fn f0<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { send_ok::<T>(); share_ok::<F>(); f(&t) }
+///     pub fn f1<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f2<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f3<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f4<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f5<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f6<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f7<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f8<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f9<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { f(&t) }
+///     pub fn f10<T, F>(t: T, f: F) -> bool where T: Component + Clone + 'static, F: for<'b> Fn(&'b T) -> bool + 'static { send_ok::<T>(); share_ok::<F>(); !f(&t) }
+/// }
+///
+/// assert!(f0(1u32, |v| *v == 1));
+/// assert!(f10(1u32, |v| *v == 2));
+/// ```
+///
+/// [`MaybeSend`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSend.html
+/// [`MaybeSync`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSync.html
+#[proc_macro]
+pub fn maybe_bounds(input: TokenStream) -> TokenStream {
+    let MaybeBoundsInput { bounds, mut items } = parse_macro_input!(input as MaybeBoundsInput);
+    for item in &mut items {
+        let where_clause = match item_generics(item) {
+            Some(generics) => match &mut generics.where_clause {
+                Some(where_clause) => where_clause,
+                None => continue,
+            },
+            None => continue,
+        };
+        for predicate in &mut where_clause.predicates {
+            if let WherePredicate::Type(predicate) = predicate {
+                predicate.bounds.extend(bounds.iter().cloned());
+            }
+        }
+    }
+    quote!(#(#items)*).into()
+}
+
+struct MaybeBoundsInput {
+    bounds: Vec<TypeParamBound>,
+    items: Vec<Item>,
+}
+
+impl Parse for MaybeBoundsInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let bounds = if input.peek(Ident) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
+            let prefix: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            if prefix == "send" {
+                vec![parse_quote!(::maybe_sync::MaybeSend)]
+            } else if prefix == "sync" {
+                vec![parse_quote!(::maybe_sync::MaybeSync)]
+            } else {
+                return Err(syn::Error::new(prefix.span(), "expected `send` or `sync`"));
+            }
+        } else {
+            vec![
+                parse_quote!(::maybe_sync::MaybeSend),
+                parse_quote!(::maybe_sync::MaybeSync),
+            ]
+        };
+
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(MaybeBoundsInput { bounds, items })
+    }
+}
+
+fn item_generics(item: &mut Item) -> Option<&mut Generics> {
+    match item {
+        Item::Const(item) => Some(&mut item.generics),
+        Item::Enum(item) => Some(&mut item.generics),
+        Item::Fn(item) => Some(&mut item.sig.generics),
+        Item::Impl(item) => Some(&mut item.generics),
+        Item::Struct(item) => Some(&mut item.generics),
+        Item::Trait(item) => Some(&mut item.generics),
+        Item::TraitAlias(item) => Some(&mut item.generics),
+        Item::Type(item) => Some(&mut item.generics),
+        Item::Union(item) => Some(&mut item.generics),
+        _ => None,
+    }
+}
+
+/// Declares trait object alias with [`MaybeSend`] and [`MaybeSync`] markers,
+/// together with boxed and reference counted variants.
+///
+/// `define_dyn_alias! { pub DynSource = Source + 'static; }` expands to
+///
+/// * `pub type DynSource = dyn_maybe_send_sync!(Source + 'static);`
+/// * `pub type BoxDynSource = Box<DynSource>;`
+/// * `pub type RcDynSource = maybe_sync::Rc<DynSource>;`
+/// * `pub fn box_dyn_source(value) -> BoxDynSource` and
+///   `pub fn rc_dyn_source(value) -> RcDynSource` constructors.
+///
+/// Constructor names are the alias name in snake case.
+/// A new word starts at a capital letter following a lowercase letter or digit,
+/// and at the last capital of a run that is followed by a lowercase letter,
+/// so runs of capitals stay one word: `DynIOSource` gets `box_dyn_io_source`.
+///
+/// Attributes and doc comments given before the alias are applied to the trait object alias.
+/// Several aliases may be declared in one invocation.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{define_dyn_alias, share_ok};
+/// pub trait Source {
+///     fn read(&self) -> Vec<u8>;
+/// }
+///
+/// struct Bytes(&'static [u8]);
+///
+/// impl Source for Bytes {
+///     fn read(&self) -> Vec<u8> {
+///         self.0.to_vec()
+///     }
+/// }
+///
+/// define_dyn_alias! {
+///     pub DynSource = Source + 'static;
+///
+///     /// Any debuggable value.
+///     DynDebug = std::fmt::Debug;
+///
+///     DynIOSource = Source;
+/// }
+///
+/// let boxed: BoxDynSource = box_dyn_source(Bytes(b"boxed"));
+/// let shared: RcDynSource = rc_dyn_source(Bytes(b"shared"));
+/// let shared_clone = shared.clone();
+///
+/// assert_eq!(boxed.read(), b"boxed");
+/// assert_eq!(shared_clone.read(), b"shared");
+/// assert_eq!(format!("{:?}", box_dyn_debug(42)), "42");
+/// assert_eq!(box_dyn_io_source(Bytes(b"io")).read(), b"io");
+///
+/// // Sendable and shareable when "sync" feature is enabled.
+/// maybe_sync::if_sync! {
+///     share_ok::<DynSource>();
+///     share_ok::<RcDynSource>();
+/// }
+/// ```
+///
+/// [`MaybeSend`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSend.html
+/// [`MaybeSync`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSync.html
+#[proc_macro]
+pub fn define_dyn_alias(input: TokenStream) -> TokenStream {
+    let aliases = parse_macro_input!(input as DynAliases);
+    let aliases = aliases.0.iter().map(DynAlias::expand);
+    quote!(#(#aliases)*).into()
+}
+
+struct DynAliases(Vec<DynAlias>);
+
+impl Parse for DynAliases {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut aliases = Vec::new();
+        while !input.is_empty() {
+            aliases.push(input.parse()?);
+        }
+        Ok(DynAliases(aliases))
+    }
+}
+
+struct DynAlias {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    bounds: Punctuated<TypeParamBound, Token![+]>,
+}
+
+impl Parse for DynAlias {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let bounds = Punctuated::parse_separated_nonempty(input)?;
+        input.parse::<Token![;]>()?;
+        Ok(DynAlias {
+            attrs,
+            vis,
+            ident,
+            bounds,
+        })
+    }
+}
+
+impl DynAlias {
+    fn expand(&self) -> TokenStream2 {
+        let DynAlias {
+            attrs,
+            vis,
+            ident,
+            bounds,
+        } = self;
+
+        let snake = snake_case(&ident.to_string());
+        let box_ident = format_ident!("Box{}", ident);
+        let rc_ident = format_ident!("Rc{}", ident);
+        let box_fn = format_ident!("box_{}", snake);
+        let rc_fn = format_ident!("rc_{}", snake);
+
+        // Link to the first trait in the bounds.
+        let trait_name = bounds
+            .iter()
+            .find_map(|bound| match bound {
+                TypeParamBound::Trait(bound) => Some(&bound.path),
+                _ => None,
+            })
+            .map(|path| {
+                let segments: Vec<_> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+                let leading = if path.leading_colon.is_some() {
+                    "::"
+                } else {
+                    ""
+                };
+                format!("{}{}", leading, segments.join("::"))
+            });
+
+        let alias_doc = match &trait_name {
+            Some(name) => format!(
+                "Trait object of [`{}`], sendable and shareable when \"sync\" feature of `maybe-sync` is enabled.",
+                name
+            ),
+            None => "Trait object, sendable and shareable when \"sync\" feature of `maybe-sync` is enabled.".to_owned(),
+        };
+        let alias_doc = if attrs.iter().any(|attr| attr.path().is_ident("doc")) {
+            quote!()
+        } else {
+            quote!(#[doc = #alias_doc])
+        };
+
+        let box_doc = format!("Boxed [`{}`].", ident);
+        let rc_doc = format!("Reference counted [`{}`].", ident);
+        let box_fn_doc = format!("Boxes the value into [`{}`].", box_ident);
+        let rc_fn_doc = format!("Puts the value into [`{}`].", rc_ident);
+
+        quote! {
+            #(#attrs)*
+            #alias_doc
+            #vis type #ident = ::maybe_sync::dyn_maybe_send_sync!(#bounds);
+
+            #[doc = #box_doc]
+            #vis type #box_ident = ::maybe_sync::__private::Box<#ident>;
+
+            #[doc = #rc_doc]
+            #vis type #rc_ident = ::maybe_sync::Rc<#ident>;
+
+            #[doc = #box_fn_doc]
+            #vis fn #box_fn<T>(value: T) -> #box_ident
+            where
+                T: #bounds + ::maybe_sync::MaybeSend + ::maybe_sync::MaybeSync + 'static,
+            {
+                ::maybe_sync::__private::Box::new(value)
+            }
+
+            #[doc = #rc_fn_doc]
+            #vis fn #rc_fn<T>(value: T) -> #rc_ident
+            where
+                T: #bounds + ::maybe_sync::MaybeSend + ::maybe_sync::MaybeSync + 'static,
+            {
+                ::maybe_sync::Rc::new(value)
+            }
+        }
+    }
+}
+
+// Word starts at capital letter that follows lowercase letter or digit,
+// or at the last capital of a run followed by lowercase letter,
+// so `DynIOSource` becomes `dyn_io_source`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (index, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = index.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(index + 1);
+            let starts_word = match prev {
+                Some(prev) if prev.is_lowercase() || prev.is_ascii_digit() => true,
+                Some(prev) if prev.is_uppercase() => next.is_some_and(|next| next.is_lowercase()),
+                _ => false,
+            };
+            if starts_word {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Asserts that every field of the type is [`MaybeSend`].
+///
+/// When the type stops being sendable with "sync" feature enabled,
+/// compile error points at the offending field type
+/// instead of the whole type.\
+/// Type parameters are assumed to be [`MaybeSend`].
+///
+/// Fields marked with `#[assert_maybe(skip)]` are not checked.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{AssertMaybeSend, AssertMaybeSync};
+/// #[derive(AssertMaybeSend, AssertMaybeSync)]
+/// struct Scene<'a, T> {
+///     name: &'a str,
+///     nodes: Vec<T>,
+///     counts: std::collections::HashMap<u64, u32>,
+///
+///     // Local field checked separately.
+///     #[assert_maybe(skip)]
+///     marker: std::marker::PhantomData<T>,
+/// }
+///
+/// #[derive(AssertMaybeSend)]
+/// enum Event<T> {
+///     Added(T),
+///     Removed { id: u64 },
+/// }
+/// ```
+///
+/// [`MaybeSend`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSend.html
+#[proc_macro_derive(AssertMaybeSend, attributes(assert_maybe))]
+pub fn derive_assert_maybe_send(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    assert_fields(&input, quote!(::maybe_sync::MaybeSend))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Asserts that every field of the type is [`MaybeSync`].
+///
+/// Works the same way as [`AssertMaybeSend`].
+///
+/// [`MaybeSync`]: https://docs.rs/maybe-sync/latest/maybe_sync/trait.MaybeSync.html
+/// [`AssertMaybeSend`]: ./derive.AssertMaybeSend.html
+#[proc_macro_derive(AssertMaybeSync, attributes(assert_maybe))]
+pub fn derive_assert_maybe_sync(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    assert_fields(&input, quote!(::maybe_sync::MaybeSync))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn assert_fields(input: &DeriveInput, marker: TokenStream2) -> syn::Result<TokenStream2> {
+    let fields: Vec<&Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(data) => data.fields.named.iter().collect(),
+    };
+
+    let mut assertions = Vec::new();
+    for field in fields {
+        if skip_field(&field.attrs)? {
+            continue;
+        }
+        let ty = &field.ty;
+        assertions.push(quote_spanned!(ty.span() => __assert_field::<#ty>();));
+    }
+
+    // Assertions are placed into generic function
+    // that assumes type parameters satisfy the bound.
+    let mut generics = input.generics.clone();
+    let type_params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    for param in type_params {
+        where_clause.predicates.push(parse_quote!(#param: #marker));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn __assert_field<T: #marker + ?Sized>() {}
+
+            #[allow(dead_code)]
+            fn __assert_fields #impl_generics () #where_clause {
+                #(#assertions)*
+            }
+        };
+    })
+}
+
+/// Returns `true` if field is marked with `#[assert_maybe(skip)]`.
+fn skip_field(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in attrs {
+        if attr.path().is_ident("assert_maybe") {
+            let arg = attr.parse_args::<Ident>()?;
+            if arg != "skip" {
+                return Err(syn::Error::new(arg.span(), "expected `skip`"));
+            }
+            skip = true;
+        }
+    }
+    Ok(skip)
+}
@@ -0,0 +1,98 @@
+//! Checks where compile errors of the derives point to.
+//!
+//! Every `tests/ui/*.rs` file is built as a binary crate
+//! depending on `maybe-sync` with "sync" feature enabled.
+//! Errors are printed in short format, one line per error,
+//! and compared with `.stderr` file next to the source.
+//!
+//! Run with `MAYBE_SYNC_BLESS=1` to overwrite `.stderr` files with actual errors.
+
+use std::{env, fs, path::Path, process::Command};
+
+#[test]
+fn ui() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest_dir.parent().unwrap();
+    let work_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ui");
+    let bless = env::var_os("MAYBE_SYNC_BLESS").is_some();
+
+    let mut cases: Vec<_> = fs::read_dir(manifest_dir.join("tests/ui"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    cases.sort();
+
+    let mut mismatches = Vec::new();
+    for case in &cases {
+        let name = case.file_stem().unwrap().to_str().unwrap();
+        let crate_dir = work_dir.join(name);
+        fs::create_dir_all(crate_dir.join("src")).unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            format!(
+                "[package]\n\
+                 name = \"ui-{}\"\n\
+                 version = \"0.0.0\"\n\
+                 edition = \"2021\"\n\
+                 publish = false\n\
+                 \n\
+                 [dependencies]\n\
+                 maybe-sync = {{ path = {:?}, features = [\"macros\", \"sync\"] }}\n\
+                 \n\
+                 [workspace]\n",
+                name, root,
+            ),
+        )
+        .unwrap();
+        fs::copy(case, crate_dir.join("src/main.rs")).unwrap();
+
+        // Reuse versions locked for the workspace, so no network is needed.
+        if let Ok(lock) = fs::read(root.join("Cargo.lock")) {
+            fs::write(crate_dir.join("Cargo.lock"), lock).unwrap();
+        }
+
+        let output = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+            .arg("check")
+            .arg("--quiet")
+            .arg("--message-format=short")
+            .arg("--manifest-path")
+            .arg(crate_dir.join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(work_dir.join("target"))
+            .env("CARGO_TERM_COLOR", "never")
+            .output()
+            .unwrap();
+
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(!output.status.success(), "`{}` compiled successfully", name);
+        let actual: String = stderr
+            .lines()
+            .filter(|line| line.starts_with("src/main.rs"))
+            .map(|line| format!("{}\n", line))
+            .collect();
+        assert!(
+            !actual.is_empty(),
+            "`{}` failed without errors in source:\n{}",
+            name,
+            stderr
+        );
+
+        let expected_path = case.with_extension("stderr");
+        if bless {
+            fs::write(&expected_path, &actual).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&expected_path).unwrap_or_default();
+        if expected.replace("\r\n", "\n") != actual {
+            mismatches.push(format!(
+                "{}\nexpected:\n{}\nactual:\n{}",
+                case.display(),
+                expected,
+                actual
+            ));
+        }
+    }
+
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
@@ -0,0 +1,15 @@
+use maybe_sync::AssertMaybeSend;
+
+#[derive(AssertMaybeSend)]
+struct Scene<T> {
+    nodes: Vec<T>,
+    cache: std::rc::Rc<Vec<u32>>,
+}
+
+#[derive(AssertMaybeSend)]
+enum Event {
+    Added(u64),
+    Renamed { id: u64, name: std::rc::Rc<str> },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use maybe_sync::AssertMaybeSync;
+
+#[derive(AssertMaybeSync)]
+struct Counter<'a> {
+    name: &'a str,
+    value: std::cell::Cell<u32>,
+}
+
+fn main() {}
@@ -0,0 +1,114 @@
+use {
+    crate::{MaybeSend, MaybeSync, Rc},
+    alloc::boxed::Box,
+    core::any::Any,
+};
+
+/// Type-erased shared pointer.
+///
+/// A type alias to `Arc<dyn Any + Send + Sync>` when "sync" feature is enabled.\
+/// A type alias to `Rc<dyn Any>` when "sync" feature is not enabled.
+pub type RcAny = Rc<crate::dyn_maybe_send_sync!(Any)>;
+
+/// Erases type of the value, putting it into [`RcAny`].
+///
+/// [`RcAny`]: ./type.RcAny.html
+pub fn rc_any<T>(value: T) -> RcAny
+where
+    T: MaybeSend + MaybeSync + 'static,
+{
+    Rc::new(value)
+}
+
+/// Attempts to downcast [`RcAny`] to a concrete type.\
+/// Returns original pointer back if type doesn't match.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{downcast_rc, rc_any, Rc};
+/// let any = rc_any(42u32);
+/// let any = downcast_rc::<String>(any).unwrap_err();
+/// let value: Rc<u32> = downcast_rc(any).unwrap();
+/// assert_eq!(*value, 42);
+/// ```
+///
+/// [`RcAny`]: ./type.RcAny.html
+pub fn downcast_rc<T>(rc: RcAny) -> Result<Rc<T>, RcAny>
+where
+    T: MaybeSend + MaybeSync + 'static,
+{
+    rc.downcast()
+}
+
+/// Type-erased box.
+///
+/// A type alias to `Box<dyn Any + Send + Sync>` when "sync" feature is enabled.\
+/// A type alias to `Box<dyn Any>` when "sync" feature is not enabled.
+pub type BoxAny = Box<crate::dyn_maybe_send_sync!(Any)>;
+
+/// Type-erased box without `Sync` requirement.
+///
+/// A type alias to `Box<dyn Any + Send>` when "sync" feature is enabled.\
+/// A type alias to `Box<dyn Any>` when "sync" feature is not enabled.
+pub type BoxAnySend = Box<crate::dyn_maybe_send!(Any)>;
+
+/// Erases type of the value, putting it into [`BoxAny`].
+///
+/// [`BoxAny`]: ./type.BoxAny.html
+pub fn box_any<T>(value: T) -> BoxAny
+where
+    T: MaybeSend + MaybeSync + 'static,
+{
+    Box::new(value)
+}
+
+/// Attempts to downcast [`BoxAny`] to a concrete type.\
+/// Returns original box back if type doesn't match.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{box_any, downcast_box};
+/// let any = box_any(42u32);
+/// let any = downcast_box::<String>(any).unwrap_err();
+/// assert_eq!(*downcast_box::<u32>(any).unwrap(), 42);
+/// ```
+///
+/// [`BoxAny`]: ./type.BoxAny.html
+pub fn downcast_box<T>(boxed: BoxAny) -> Result<Box<T>, BoxAny>
+where
+    T: MaybeSend + MaybeSync + 'static,
+{
+    boxed.downcast()
+}
+
+/// Erases type of the value, putting it into [`BoxAnySend`].
+///
+/// [`BoxAnySend`]: ./type.BoxAnySend.html
+pub fn box_any_send<T>(value: T) -> BoxAnySend
+where
+    T: MaybeSend + 'static,
+{
+    Box::new(value)
+}
+
+/// Attempts to downcast [`BoxAnySend`] to a concrete type.\
+/// Returns original box back if type doesn't match.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{box_any_send, downcast_box_send}, std::cell::Cell};
+/// let any = box_any_send(Cell::new(42u32));
+/// let any = downcast_box_send::<u32>(any).unwrap_err();
+/// assert_eq!(downcast_box_send::<Cell<u32>>(any).unwrap().get(), 42);
+/// ```
+///
+/// [`BoxAnySend`]: ./type.BoxAnySend.html
+pub fn downcast_box_send<T>(boxed: BoxAnySend) -> Result<Box<T>, BoxAnySend>
+where
+    T: MaybeSend + 'static,
+{
+    boxed.downcast()
+}
@@ -0,0 +1,231 @@
+use {
+    crate::{AtomicPtr, AtomicUsize},
+    alloc::boxed::Box,
+    core::{fmt, marker::PhantomData, mem::MaybeUninit, ptr},
+};
+
+#[cfg(feature = "sync")]
+use {crate::Mutex, core::sync::atomic::Ordering};
+
+// First bucket holds `1 << FIRST_BUCKET_SHIFT` elements.
+// Each next bucket is twice as large as previous.
+const FIRST_BUCKET_SHIFT: u32 = 3;
+const BUCKETS: usize = (usize::BITS - FIRST_BUCKET_SHIFT) as usize;
+
+/// Returns bucket index and offset within the bucket for element index.
+fn location(index: usize) -> (usize, usize) {
+    let i = index + (1 << FIRST_BUCKET_SHIFT);
+    let shift = usize::BITS - 1 - i.leading_zeros();
+    let bucket = (shift - FIRST_BUCKET_SHIFT) as usize;
+    (bucket, i - (1 << shift))
+}
+
+fn bucket_capacity(bucket: usize) -> usize {
+    1 << (bucket as u32 + FIRST_BUCKET_SHIFT)
+}
+
+/// Vector that can only grow and can be pushed to through shared reference.
+///
+/// Elements are never moved once pushed,
+/// so references returned by [`get`] stay valid as the vector grows.
+///
+/// Pushes are serialized with a lock and readers never block
+/// when "sync" feature is enabled.\
+/// No synchronization happens when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::AppendOnlyVec;
+/// let log = AppendOnlyVec::new();
+/// let first = log.push("started");
+/// let first_ref = log.get(first).unwrap();
+///
+/// for _ in 0..100 {
+///     log.push("event");
+/// }
+///
+/// assert_eq!(*first_ref, "started");
+/// assert_eq!(log.len(), 101);
+/// assert_eq!(log.get(101), None);
+///
+/// #[cfg(feature = "sync")]
+/// {
+///     let log = AppendOnlyVec::new();
+///     std::thread::scope(|scope| {
+///         for t in 0..4 {
+///             let log = &log;
+///             scope.spawn(move || {
+///                 for i in 0..1000 {
+///                     log.push(t * 1000 + i);
+///                 }
+///             });
+///         }
+///     });
+///
+///     let mut values: Vec<_> = log.iter().copied().collect();
+///     values.sort();
+///     assert_eq!(values, (0..4000).collect::<Vec<_>>());
+/// }
+/// ```
+///
+/// [`get`]: ./struct.AppendOnlyVec.html#method.get
+pub struct AppendOnlyVec<T> {
+    buckets: [AtomicPtr<T>; BUCKETS],
+    len: AtomicUsize,
+    #[cfg(feature = "sync")]
+    lock: Mutex<()>,
+    marker: PhantomData<T>,
+}
+
+// Vector owns its elements.
+unsafe impl<T> Send for AppendOnlyVec<T> where T: Send {}
+
+// Values may be pushed from any thread sharing the vector.
+#[cfg(feature = "sync")]
+unsafe impl<T> Sync for AppendOnlyVec<T> where T: Send + Sync {}
+
+#[cfg(feature = "sync")]
+impl<T> AppendOnlyVec<T> {
+    fn load_len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    fn store_len(&self, len: usize) {
+        self.len.store(len, Ordering::Release)
+    }
+
+    fn load_bucket(&self, bucket: usize) -> *mut T {
+        self.buckets[bucket].load(Ordering::Acquire)
+    }
+
+    fn store_bucket(&self, bucket: usize, ptr: *mut T) {
+        self.buckets[bucket].store(ptr, Ordering::Release)
+    }
+}
+
+#[cfg(not(feature = "sync"))]
+impl<T> AppendOnlyVec<T> {
+    fn load_len(&self) -> usize {
+        self.len.get()
+    }
+
+    fn store_len(&self, len: usize) {
+        self.len.set(len)
+    }
+
+    fn load_bucket(&self, bucket: usize) -> *mut T {
+        self.buckets[bucket].get()
+    }
+
+    fn store_bucket(&self, bucket: usize, ptr: *mut T) {
+        self.buckets[bucket].set(ptr)
+    }
+}
+
+impl<T> AppendOnlyVec<T> {
+    /// Creates new empty vector.\
+    /// This function does not allocate.
+    pub fn new() -> Self {
+        AppendOnlyVec {
+            buckets: [(); BUCKETS].map(|()| AtomicPtr::new(ptr::null_mut())),
+            len: AtomicUsize::new(0),
+            #[cfg(feature = "sync")]
+            lock: Mutex::new(()),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns number of elements in the vector.
+    pub fn len(&self) -> usize {
+        self.load_len()
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.load_len() == 0
+    }
+
+    /// Appends an element to the back of the vector.\
+    /// Returns index of the element.
+    pub fn push(&self, value: T) -> usize {
+        #[cfg(feature = "sync")]
+        let _guard = self.lock.lock();
+
+        let index = self.load_len();
+        let (bucket, offset) = location(index);
+        let mut ptr = self.load_bucket(bucket);
+        if ptr.is_null() {
+            let storage = Box::<[T]>::new_uninit_slice(bucket_capacity(bucket));
+            ptr = Box::into_raw(storage).cast::<T>();
+            self.store_bucket(bucket, ptr);
+        }
+
+        // Safety: slot is within the bucket and is not initialized yet.
+        // Readers don't access it until length is updated.
+        unsafe { ptr.add(offset).write(value) };
+        self.store_len(index + 1);
+        index
+    }
+
+    /// Returns reference to the element at the index
+    /// or `None` if index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.load_len() {
+            return None;
+        }
+
+        let (bucket, offset) = location(index);
+        let ptr = self.load_bucket(bucket);
+
+        // Safety: element is initialized and is never moved or mutated.
+        Some(unsafe { &*ptr.add(offset) })
+    }
+
+    /// Returns iterator over elements pushed so far.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.load_len()).map(move |index| self.get(index).unwrap())
+    }
+}
+
+impl<T> Default for AppendOnlyVec<T> {
+    fn default() -> Self {
+        AppendOnlyVec::new()
+    }
+}
+
+impl<T> fmt::Debug for AppendOnlyVec<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Drop for AppendOnlyVec<T> {
+    fn drop(&mut self) {
+        let mut len = self.load_len();
+
+        for bucket in 0..BUCKETS {
+            let ptr = self.load_bucket(bucket);
+            if ptr.is_null() {
+                break;
+            }
+
+            let capacity = bucket_capacity(bucket);
+            let initialized = len.min(capacity);
+            len -= initialized;
+
+            // Safety: first `initialized` elements of the bucket are initialized.
+            // Bucket was allocated as boxed slice of `capacity` elements.
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, initialized));
+                drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+                    ptr.cast::<MaybeUninit<T>>(),
+                    capacity,
+                )));
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use {
+    alloc::boxed::Box,
+    core::pin::Pin,
+    futures_io::{AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite},
+};
+
+/// Boxed [`AsyncRead`] trait object.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::BoxAsyncRead, futures_io::AsyncRead, std::task::{Context, Poll, Waker}};
+/// trait Source {
+///     fn open(&self) -> BoxAsyncRead<'_>;
+/// }
+///
+/// struct Memory(Vec<u8>);
+///
+/// impl Source for Memory {
+///     fn open(&self) -> BoxAsyncRead<'_> {
+///         Box::pin(&self.0[..])
+///     }
+/// }
+///
+/// let source = Memory(b"data".to_vec());
+/// let mut reader = source.open();
+/// let mut buf = [0; 8];
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let poll = reader.as_mut().poll_read(&mut cx, &mut buf);
+/// assert!(matches!(poll, Poll::Ready(Ok(4))));
+/// assert_eq!(&buf[..4], b"data");
+/// ```
+///
+/// [`AsyncRead`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncRead.html
+pub type BoxAsyncRead<'a> = Pin<Box<crate::dyn_maybe_send!(AsyncRead + 'a)>>;
+
+/// Boxed [`AsyncWrite`] trait object.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// [`AsyncWrite`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncWrite.html
+pub type BoxAsyncWrite<'a> = Pin<Box<crate::dyn_maybe_send!(AsyncWrite + 'a)>>;
+
+/// Boxed [`AsyncBufRead`] trait object.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::BoxAsyncBufRead, futures_io::AsyncBufRead, std::task::{Context, Poll, Waker}};
+/// let mut reader: BoxAsyncBufRead<'_> = Box::pin(&b"data"[..]);
+/// let mut cx = Context::from_waker(Waker::noop());
+/// match reader.as_mut().poll_fill_buf(&mut cx) {
+///     Poll::Ready(Ok(buf)) => assert_eq!(buf, b"data"),
+///     _ => panic!("Slice reader is always ready"),
+/// }
+/// ```
+///
+/// [`AsyncBufRead`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncBufRead.html
+pub type BoxAsyncBufRead<'a> = Pin<Box<crate::dyn_maybe_send!(AsyncBufRead + 'a)>>;
+
+/// Boxed [`AsyncSeek`] trait object.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// [`AsyncSeek`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncSeek.html
+pub type BoxAsyncSeek<'a> = Pin<Box<crate::dyn_maybe_send!(AsyncSeek + 'a)>>;
@@ -0,0 +1,416 @@
+use crate::{
+    AtomicBool, AtomicI16, AtomicI32, AtomicI8, AtomicIsize, AtomicPtr, AtomicU16, AtomicU32,
+    AtomicU8, AtomicUsize,
+};
+
+#[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+use crate::AtomicU64;
+
+#[cfg(feature = "sync")]
+use core::sync::atomic::Ordering;
+
+/// Extension methods for atomic types that work the same way
+/// whether "sync" feature is enabled or not.
+///
+/// Atomic types are `Clone` when "sync" feature is not enabled,
+/// as they are aliases to `Cell`, but not when "sync" feature is enabled.
+/// Use [`clone_value`] to copy atomic value in both cases.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{AtomicExt, AtomicUsize};
+/// struct Counters {
+///     hits: AtomicUsize,
+///     misses: AtomicUsize,
+/// }
+///
+/// let counters = Counters { hits: AtomicUsize::new(3), misses: AtomicUsize::new(1) };
+/// let snapshot = Counters {
+///     hits: counters.hits.clone_value(),
+///     misses: counters.misses.clone_value(),
+/// };
+/// assert_eq!(snapshot.hits.into_inner(), 3);
+/// assert_eq!(snapshot.misses.into_inner(), 1);
+/// ```
+///
+/// [`clone_value`]: ./trait.AtomicExt.html#tymethod.clone_value
+pub trait AtomicExt {
+    /// Creates new atomic initialized with current value of this one.\
+    /// Value is loaded with `Relaxed` ordering when "sync" feature is enabled.
+    fn clone_value(&self) -> Self;
+}
+
+macro_rules! impl_atomic_ext {
+    ($($atomic:ty),* $(,)?) => {
+        $(
+            impl AtomicExt for $atomic {
+                #[cfg(feature = "sync")]
+                fn clone_value(&self) -> Self {
+                    <$atomic>::new(self.load(Ordering::Relaxed))
+                }
+
+                #[cfg(not(feature = "sync"))]
+                fn clone_value(&self) -> Self {
+                    <$atomic>::new(self.get())
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_ext!(
+    AtomicBool,
+    AtomicI8,
+    AtomicI16,
+    AtomicI32,
+    AtomicIsize,
+    AtomicU8,
+    AtomicU16,
+    AtomicU32,
+    AtomicUsize,
+);
+
+#[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+impl_atomic_ext!(crate::AtomicI64, crate::AtomicU64);
+
+#[cfg(feature = "portable-atomic")]
+impl_atomic_ext!(crate::AtomicI128, crate::AtomicU128);
+
+impl<T> AtomicExt for AtomicPtr<T> {
+    #[cfg(feature = "sync")]
+    fn clone_value(&self) -> Self {
+        AtomicPtr::new(self.load(Ordering::Relaxed))
+    }
+
+    #[cfg(not(feature = "sync"))]
+    fn clone_value(&self) -> Self {
+        AtomicPtr::new(self.get())
+    }
+}
+
+/// Boolean flag for run-once and signaling patterns.
+///
+/// Wraps [`AtomicBool`] using `Acquire`/`Release` orderings
+/// when "sync" feature is enabled, so that writes made before raising the flag
+/// are visible to the thread that observes it raised.\
+/// Uses plain `Cell` operations when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::Flag;
+/// let init = Flag::new(false);
+///
+/// let mut runs = 0;
+/// for _ in 0..3 {
+///     if !init.raise() {
+///         runs += 1;
+///     }
+/// }
+///
+/// assert_eq!(runs, 1);
+/// assert!(init.is_raised());
+///
+/// init.reset();
+/// assert!(!init.is_raised());
+/// assert!(!init.raise());
+/// ```
+///
+/// Only one thread wins the race to raise the flag.
+///
+/// ```
+/// # use maybe_sync::{AtomicUsize, Flag, Rc};
+/// #[cfg(feature = "sync")]
+/// {
+///     use std::sync::atomic::Ordering;
+///
+///     let init = Rc::new(Flag::new(false));
+///     let runs = Rc::new(AtomicUsize::new(0));
+///
+///     let threads: Vec<_> = (0..4)
+///         .map(|_| {
+///             let (init, runs) = (init.clone(), runs.clone());
+///             std::thread::spawn(move || {
+///                 if !init.raise() {
+///                     runs.fetch_add(1, Ordering::Relaxed);
+///                 }
+///             })
+///         })
+///         .collect();
+///
+///     for thread in threads {
+///         thread.join().unwrap();
+///     }
+///     assert_eq!(runs.load(Ordering::Relaxed), 1);
+/// }
+/// ```
+///
+/// [`AtomicBool`]: ./type.AtomicBool.html
+#[derive(Default)]
+pub struct Flag {
+    raised: AtomicBool,
+}
+
+impl Flag {
+    /// Creates new flag.
+    pub const fn new(raised: bool) -> Self {
+        Flag {
+            raised: AtomicBool::new(raised),
+        }
+    }
+
+    /// Raises the flag.\
+    /// Returns `true` if the flag was already raised.
+    #[cfg(feature = "sync")]
+    pub fn raise(&self) -> bool {
+        self.raised.swap(true, Ordering::AcqRel)
+    }
+
+    /// Raises the flag.\
+    /// Returns `true` if the flag was already raised.
+    #[cfg(not(feature = "sync"))]
+    pub fn raise(&self) -> bool {
+        self.raised.replace(true)
+    }
+
+    /// Returns `true` if the flag is raised.
+    #[cfg(feature = "sync")]
+    pub fn is_raised(&self) -> bool {
+        self.raised.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if the flag is raised.
+    #[cfg(not(feature = "sync"))]
+    pub fn is_raised(&self) -> bool {
+        self.raised.get()
+    }
+
+    /// Lowers the flag.
+    #[cfg(feature = "sync")]
+    pub fn reset(&self) {
+        self.raised.store(false, Ordering::Release)
+    }
+
+    /// Lowers the flag.
+    #[cfg(not(feature = "sync"))]
+    pub fn reset(&self) {
+        self.raised.set(false)
+    }
+}
+
+impl core::fmt::Debug for Flag {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Flag")
+            .field("raised", &self.is_raised())
+            .finish()
+    }
+}
+
+/// Counter for metrics that are updated occasionally and read often.
+///
+/// Wraps [`AtomicUsize`] using `Relaxed` ordering when "sync" feature is enabled,
+/// so it provides no synchronization with other memory.\
+/// Uses plain `Cell` operations when "sync" feature is not enabled.
+///
+/// Wraps around on overflow in both cases.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::Counter;
+/// let requests = Counter::new(0);
+/// for _ in 0..1000 {
+///     requests.add(1);
+/// }
+/// requests.sub(10);
+/// assert_eq!(requests.get(), 990);
+/// ```
+///
+/// ```
+/// # use maybe_sync::Counter;
+/// #[cfg(feature = "sync")]
+/// {
+///     let hits = Counter::new(0);
+///     std::thread::scope(|scope| {
+///         for _ in 0..8 {
+///             scope.spawn(|| {
+///                 for _ in 0..10_000 {
+///                     hits.add(1);
+///                 }
+///             });
+///         }
+///     });
+///     assert_eq!(hits.snapshot(), 80_000);
+/// }
+/// ```
+///
+/// [`AtomicUsize`]: ./type.AtomicUsize.html
+#[derive(Default)]
+pub struct Counter {
+    value: AtomicUsize,
+}
+
+impl Counter {
+    /// Creates new counter.
+    pub const fn new(value: usize) -> Self {
+        Counter {
+            value: AtomicUsize::new(value),
+        }
+    }
+
+    /// Adds to the counter.
+    #[cfg(feature = "sync")]
+    pub fn add(&self, n: usize) {
+        self.value.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Adds to the counter.
+    #[cfg(not(feature = "sync"))]
+    pub fn add(&self, n: usize) {
+        self.value.set(self.value.get().wrapping_add(n));
+    }
+
+    /// Subtracts from the counter.
+    #[cfg(feature = "sync")]
+    pub fn sub(&self, n: usize) {
+        self.value.fetch_sub(n, Ordering::Relaxed);
+    }
+
+    /// Subtracts from the counter.
+    #[cfg(not(feature = "sync"))]
+    pub fn sub(&self, n: usize) {
+        self.value.set(self.value.get().wrapping_sub(n));
+    }
+
+    /// Returns current value.
+    #[cfg(feature = "sync")]
+    pub fn get(&self) -> usize {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    /// Returns current value.
+    #[cfg(not(feature = "sync"))]
+    pub fn get(&self) -> usize {
+        self.value.get()
+    }
+
+    /// Returns current value for reporting.\
+    /// Same as [`get`].
+    ///
+    /// [`get`]: ./struct.Counter.html#method.get
+    pub fn snapshot(&self) -> usize {
+        self.get()
+    }
+}
+
+impl core::fmt::Debug for Counter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Counter").field(&self.get()).finish()
+    }
+}
+
+/// Allocator of unique `u64` identifiers.
+///
+/// Wraps [`AtomicU64`], so identifiers can be allocated from multiple threads
+/// when "sync" feature is enabled.\
+/// Identifiers are allocated sequentially starting from zero.
+///
+/// # Panics
+///
+/// [`alloc`] panics when all `u64` values are exhausted instead of wrapping around,
+/// so returned identifiers are never repeated unless [`reset`] is called.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::GenIdAllocator;
+/// let ids = GenIdAllocator::new();
+/// assert_eq!(ids.alloc(), 0);
+/// assert_eq!(ids.alloc(), 1);
+///
+/// ids.reset();
+/// assert_eq!(ids.alloc(), 0);
+/// ```
+///
+/// Concurrent allocations never produce the same identifier.
+///
+/// ```
+/// # use maybe_sync::GenIdAllocator;
+/// #[cfg(feature = "sync")]
+/// {
+///     let allocator = GenIdAllocator::new();
+///     let mut ids: Vec<u64> = std::thread::scope(|scope| {
+///         let threads: Vec<_> = (0..4)
+///             .map(|_| scope.spawn(|| (0..1000).map(|_| allocator.alloc()).collect::<Vec<_>>()))
+///             .collect();
+///         threads.into_iter().flat_map(|t| t.join().unwrap()).collect()
+///     });
+///
+///     ids.sort_unstable();
+///     ids.dedup();
+///     assert_eq!(ids.len(), 4000);
+///     assert_eq!(ids.last(), Some(&3999));
+/// }
+/// ```
+///
+/// [`AtomicU64`]: ./type.AtomicU64.html
+/// [`alloc`]: ./struct.GenIdAllocator.html#method.alloc
+/// [`reset`]: ./struct.GenIdAllocator.html#method.reset
+#[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+#[derive(Default)]
+pub struct GenIdAllocator {
+    next: AtomicU64,
+}
+
+#[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+impl GenIdAllocator {
+    /// Creates new allocator.
+    pub const fn new() -> Self {
+        GenIdAllocator {
+            next: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns new unique identifier.
+    #[cfg(feature = "sync")]
+    pub fn alloc(&self) -> u64 {
+        self.next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| id.checked_add(1))
+            .expect("`GenIdAllocator` is exhausted")
+    }
+
+    /// Returns new unique identifier.
+    #[cfg(not(feature = "sync"))]
+    pub fn alloc(&self) -> u64 {
+        let id = self.next.get();
+        self.next
+            .set(id.checked_add(1).expect("`GenIdAllocator` is exhausted"));
+        id
+    }
+
+    /// Starts allocating identifiers from zero again.\
+    /// Previously allocated identifiers will be returned again,
+    /// so this is intended for tests.
+    #[cfg(feature = "sync")]
+    pub fn reset(&self) {
+        self.next.store(0, Ordering::Relaxed)
+    }
+
+    /// Starts allocating identifiers from zero again.\
+    /// Previously allocated identifiers will be returned again,
+    /// so this is intended for tests.
+    #[cfg(not(feature = "sync"))]
+    pub fn reset(&self) {
+        self.next.set(0)
+    }
+}
+
+#[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+impl core::fmt::Debug for GenIdAllocator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GenIdAllocator")
+            .field("next", &self.next)
+            .finish()
+    }
+}
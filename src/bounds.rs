@@ -0,0 +1,53 @@
+use {crate::MaybeSend, core::future::Future};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Shorthand for `Future + MaybeSend` bound.\
+/// Sealed and implemented for all futures that satisfy [`MaybeSend`].
+///
+/// It is intended to be used as trait bound only, not as trait object type.
+/// `Output` associated type is inherited from [`Future`]
+/// and can be constrained as usual.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{future::now_or_never, MaybeSendFuture};
+/// fn run<F: MaybeSendFuture<Output = u32>>(f: F) -> u32 {
+///     now_or_never(Box::pin(f)).unwrap()
+/// }
+///
+/// assert_eq!(run(async { 42 }), 42);
+/// ```
+///
+/// Output type is specified with associated type binding,
+/// including argument position `impl Trait`.
+///
+/// ```
+/// # use maybe_sync::{future::now_or_never, MaybeSendFuture};
+/// fn run(f: impl MaybeSendFuture<Output = u32>) -> Option<u32> {
+///     now_or_never(Box::pin(f))
+/// }
+///
+/// assert_eq!(run(async { 42 }), Some(42));
+/// ```
+///
+/// Non-sendable futures satisfy this bound only when "sync" feature is not enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::MaybeSendFuture, std::rc::Rc};
+/// fn run<F: MaybeSendFuture<Output = ()>>(_: F) {}
+///
+/// let rc = Rc::new(42);
+/// run(async move { drop(rc) });
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`Future`]: https://doc.rust-lang.org/core/future/trait.Future.html
+pub trait MaybeSendFuture: Future + MaybeSend + sealed::Sealed {}
+
+impl<F> sealed::Sealed for F where F: Future + MaybeSend + ?Sized {}
+impl<F> MaybeSendFuture for F where F: Future + MaybeSend + ?Sized {}
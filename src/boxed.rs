@@ -0,0 +1,312 @@
+use {
+    crate::{BoxFuture, MaybeSend, MaybeSendFuture, MaybeSync},
+    alloc::boxed::Box,
+};
+
+/// Boxed iterator trait object.
+///
+/// A type alias to `Box<dyn Iterator<Item = T> + Send + 'a>`
+/// when "sync" feature is enabled.\
+/// A type alias to `Box<dyn Iterator<Item = T> + 'a>`
+/// when "sync" feature is not enabled.
+pub type BoxIter<'a, T> = Box<crate::dyn_maybe_send!(Iterator<Item = T> + 'a)>;
+
+/// Boxes iterator into [`BoxIter`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{boxed_iter, BoxIter};
+/// trait Directory {
+///     fn entries(&self) -> BoxIter<'_, &str>;
+/// }
+///
+/// struct Static(Vec<&'static str>);
+///
+/// impl Directory for Static {
+///     fn entries(&self) -> BoxIter<'_, &str> {
+///         boxed_iter(self.0.iter().copied())
+///     }
+/// }
+///
+/// let dir = Static(vec!["a", "b"]);
+/// assert_eq!(dir.entries().collect::<Vec<_>>(), ["a", "b"]);
+/// ```
+///
+/// [`BoxIter`]: ./type.BoxIter.html
+pub fn boxed_iter<'a, T>(it: impl Iterator<Item = T> + MaybeSend + 'a) -> BoxIter<'a, T> {
+    Box::new(it)
+}
+
+/// Boxed double-ended iterator trait object.
+///
+/// A type alias to `Box<dyn DoubleEndedIterator<Item = T> + Send + 'a>`
+/// when "sync" feature is enabled.\
+/// A type alias to `Box<dyn DoubleEndedIterator<Item = T> + 'a>`
+/// when "sync" feature is not enabled.
+pub type BoxDoubleEndedIter<'a, T> =
+    Box<crate::dyn_maybe_send!(DoubleEndedIterator<Item = T> + 'a)>;
+
+/// Boxes double-ended iterator into [`BoxDoubleEndedIter`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::boxed_double_ended_iter;
+/// let mut it = boxed_double_ended_iter(1..4);
+/// assert_eq!(it.next_back(), Some(3));
+/// assert_eq!(it.next(), Some(1));
+/// ```
+///
+/// [`BoxDoubleEndedIter`]: ./type.BoxDoubleEndedIter.html
+pub fn boxed_double_ended_iter<'a, T>(
+    it: impl DoubleEndedIterator<Item = T> + MaybeSend + 'a,
+) -> BoxDoubleEndedIter<'a, T> {
+    Box::new(it)
+}
+
+/// Alias to [`BoxIter`] under the name used by `std` trait.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{boxed_iter, BoxIter, BoxIterator};
+/// trait Source {
+///     fn entries(&self) -> BoxIterator<'_, u32>;
+/// }
+///
+/// let entries: BoxIter<'_, u32> = boxed_iter(0..3);
+/// let entries: BoxIterator<'_, u32> = entries;
+/// assert_eq!(entries.sum::<u32>(), 3);
+/// ```
+///
+/// [`BoxIter`]: ./type.BoxIter.html
+pub type BoxIterator<'a, T> = BoxIter<'a, T>;
+
+/// Alias to [`BoxDoubleEndedIter`] under the name used by `std` trait.
+///
+/// [`BoxDoubleEndedIter`]: ./type.BoxDoubleEndedIter.html
+pub type BoxDoubleEndedIterator<'a, T> = BoxDoubleEndedIter<'a, T>;
+
+/// Extension trait for iterators to box them into [`BoxIter`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{BoxIter, MaybeSendIteratorExt};
+/// let it: BoxIter<'static, u32> = (0..3).boxed_maybe();
+/// assert_eq!(it.sum::<u32>(), 3);
+/// ```
+///
+/// Non-sendable iterators can be boxed only when "sync" feature is not enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::MaybeSendIteratorExt, std::rc::Rc};
+/// let rc = Rc::new(42);
+/// let _it = std::iter::once(rc).boxed_maybe();
+/// ```
+///
+/// [`BoxIter`]: ./type.BoxIter.html
+pub trait MaybeSendIteratorExt: Iterator {
+    /// Boxes the iterator into [`BoxIter`].
+    ///
+    /// [`BoxIter`]: ./type.BoxIter.html
+    fn boxed_maybe<'a>(self) -> BoxIter<'a, Self::Item>
+    where
+        Self: Sized + MaybeSend + 'a,
+    {
+        Box::new(self)
+    }
+}
+
+impl<I> MaybeSendIteratorExt for I where I: Iterator + ?Sized {}
+
+/// Object-safe counterpart of `Fn` trait with arguments packed into a tuple.
+///
+/// Implemented for all closures and functions with up to 6 arguments.
+pub trait CallFn<Args, Out> {
+    /// Calls the function with packed arguments.
+    fn call(&self, args: Args) -> Out;
+}
+
+/// Object-safe counterpart of `FnMut` trait with arguments packed into a tuple.
+///
+/// Implemented for all closures and functions with up to 6 arguments.
+pub trait CallFnMut<Args, Out> {
+    /// Calls the function with packed arguments.
+    fn call_mut(&mut self, args: Args) -> Out;
+}
+
+/// Object-safe counterpart of `FnOnce` trait with arguments packed into a tuple.
+///
+/// Implemented for all closures and functions with up to 6 arguments.
+pub trait CallFnOnce<Args, Out> {
+    /// Calls boxed function with packed arguments.
+    fn call_once(self: Box<Self>, args: Args) -> Out;
+}
+
+macro_rules! impl_call_fn {
+    ($($arg:ident),*) => {
+        impl<F, Out, $($arg),*> CallFn<($($arg,)*), Out> for F
+        where
+            F: Fn($($arg),*) -> Out,
+        {
+            #[allow(non_snake_case)]
+            fn call(&self, ($($arg,)*): ($($arg,)*)) -> Out {
+                self($($arg),*)
+            }
+        }
+
+        impl<F, Out, $($arg),*> CallFnMut<($($arg,)*), Out> for F
+        where
+            F: FnMut($($arg),*) -> Out,
+        {
+            #[allow(non_snake_case)]
+            fn call_mut(&mut self, ($($arg,)*): ($($arg,)*)) -> Out {
+                self($($arg),*)
+            }
+        }
+
+        impl<F, Out, $($arg),*> CallFnOnce<($($arg,)*), Out> for F
+        where
+            F: FnOnce($($arg),*) -> Out,
+        {
+            #[allow(non_snake_case)]
+            fn call_once(self: Box<Self>, ($($arg,)*): ($($arg,)*)) -> Out {
+                (*self)($($arg),*)
+            }
+        }
+    };
+}
+
+impl_call_fn!();
+impl_call_fn!(A);
+impl_call_fn!(A, B);
+impl_call_fn!(A, B, C);
+impl_call_fn!(A, B, C, D);
+impl_call_fn!(A, B, C, D, E);
+impl_call_fn!(A, B, C, D, E, G);
+
+/// Boxed `Fn` trait object with arguments packed into a tuple.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{box_fn, BoxFn, CallFn};
+/// let add: BoxFn<(u32, u32), u32> = box_fn(|a, b| a + b);
+/// assert_eq!(add.call((1, 2)), 3);
+/// assert_eq!(add.call((3, 4)), 7);
+/// ```
+pub type BoxFn<Args, Out = ()> = Box<crate::dyn_maybe_send!(CallFn<Args, Out>)>;
+
+/// Boxed `FnMut` trait object with arguments packed into a tuple.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{box_fn_mut, BoxFnMut, CallFnMut};
+/// struct Widget {
+///     on_click: BoxFnMut<(u32,), u32>,
+/// }
+///
+/// let mut clicks = 0;
+/// let mut widget = Widget {
+///     on_click: box_fn_mut(move |x| { clicks += x; clicks }),
+/// };
+/// assert_eq!(widget.on_click.call_mut((1,)), 1);
+/// assert_eq!(widget.on_click.call_mut((2,)), 3);
+/// ```
+pub type BoxFnMut<Args, Out = ()> = Box<crate::dyn_maybe_send!(CallFnMut<Args, Out>)>;
+
+/// Boxed `FnOnce` trait object with arguments packed into a tuple.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{box_fn_once, BoxFnOnce, CallFnOnce};
+/// let name = String::from("done");
+/// let finish: BoxFnOnce<(), String> = box_fn_once(move || name);
+/// assert_eq!(finish.call_once(()), "done");
+/// ```
+pub type BoxFnOnce<Args, Out = ()> = Box<crate::dyn_maybe_send!(CallFnOnce<Args, Out>)>;
+
+/// Boxes function into [`BoxFn`].
+///
+/// [`BoxFn`]: ./type.BoxFn.html
+pub fn box_fn<Args, Out>(f: impl CallFn<Args, Out> + MaybeSend + 'static) -> BoxFn<Args, Out> {
+    Box::new(f)
+}
+
+/// Boxes function into [`BoxFnMut`].
+///
+/// [`BoxFnMut`]: ./type.BoxFnMut.html
+pub fn box_fn_mut<Args, Out>(
+    f: impl CallFnMut<Args, Out> + MaybeSend + 'static,
+) -> BoxFnMut<Args, Out> {
+    Box::new(f)
+}
+
+/// Boxes function into [`BoxFnOnce`].
+///
+/// [`BoxFnOnce`]: ./type.BoxFnOnce.html
+pub fn box_fn_once<Args, Out>(
+    f: impl CallFnOnce<Args, Out> + MaybeSend + 'static,
+) -> BoxFnOnce<Args, Out> {
+    Box::new(f)
+}
+
+/// Boxed asynchronous function trait object.\
+/// Takes request and returns boxed future of response.
+///
+/// A type alias to `Box<dyn Fn(Req) -> BoxFuture<'static, Res> + Send + Sync>`
+/// when "sync" feature is enabled.\
+/// A type alias to `Box<dyn Fn(Req) -> BoxFuture<'static, Res>>`
+/// when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{box_async_fn, future::now_or_never, BoxAsyncFn};
+/// async fn double(x: u32) -> u32 {
+///     x * 2
+/// }
+///
+/// let handler: BoxAsyncFn<u32, u32> = box_async_fn(double);
+/// assert_eq!(now_or_never(handler(1)), Some(2));
+/// assert_eq!(now_or_never(handler(2)), Some(4));
+/// ```
+///
+/// Handlers capturing non-sendable values can be boxed only
+/// when "sync" feature is not enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::{box_async_fn, future::now_or_never}, std::rc::Rc};
+/// let offset = Rc::new(1);
+/// let handler = box_async_fn(move |x: u32| {
+///     let offset = offset.clone();
+///     async move { x + *offset }
+/// });
+/// assert_eq!(now_or_never(handler(1)), Some(2));
+/// ```
+pub type BoxAsyncFn<Req, Res> =
+    Box<crate::dyn_maybe_send_sync!(Fn(Req) -> BoxFuture<'static, Res>)>;
+
+/// Boxes asynchronous function into [`BoxAsyncFn`].\
+/// Futures returned by the function are boxed on each call.
+///
+/// [`BoxAsyncFn`]: ./type.BoxAsyncFn.html
+pub fn box_async_fn<Req, Res, F, Fut>(f: F) -> BoxAsyncFn<Req, Res>
+where
+    F: Fn(Req) -> Fut + MaybeSend + MaybeSync + 'static,
+    Fut: MaybeSendFuture<Output = Res> + 'static,
+{
+    Box::new(move |req| -> BoxFuture<'static, Res> { Box::pin(f(req)) })
+}
@@ -0,0 +1,110 @@
+use {
+    crate::{Rc, RwLock},
+    core::{
+        borrow::Borrow,
+        hash::{BuildHasher, Hash},
+    },
+    std::collections::{hash_map::RandomState, HashMap},
+};
+
+/// Map of shared values optimized for frequent reads and rare writes.
+///
+/// Lookups take shared read lock and insertions take exclusive write lock
+/// of a single [`RwLock`] when "sync" feature is enabled.\
+/// `RefCell`-backed map when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{ReadMostlyCache, Rc};
+/// let cache = ReadMostlyCache::new();
+/// cache.insert("config", Rc::new(42));
+/// assert_eq!(cache.get("config").as_deref(), Some(&42));
+///
+/// #[cfg(feature = "sync")]
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             for _ in 0..1000 {
+///                 assert_eq!(cache.get("config").as_deref(), Some(&42));
+///             }
+///         });
+///     }
+/// });
+/// ```
+///
+/// [`RwLock`]: ./type.RwLock.html
+pub struct ReadMostlyCache<K, V, S = RandomState> {
+    map: RwLock<HashMap<K, Rc<V>, S>>,
+}
+
+impl<K, V> ReadMostlyCache<K, V> {
+    /// Creates new empty cache.
+    pub fn new() -> Self {
+        ReadMostlyCache {
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, V, S> ReadMostlyCache<K, V, S> {
+    /// Creates new empty cache which will use the given hash builder to hash keys.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        ReadMostlyCache {
+            map: RwLock::new(HashMap::with_hasher(hash_builder)),
+        }
+    }
+
+    /// Returns number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.map.read().len()
+    }
+
+    /// Returns `true` if the cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.read().is_empty()
+    }
+}
+
+impl<K, V, S> ReadMostlyCache<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    /// Returns value associated with the key.\
+    /// Takes shared read lock.
+    pub fn get<Q>(&self, key: &Q) -> Option<Rc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.read().get(key).cloned()
+    }
+
+    /// Associates value with the key.\
+    /// Returns previously associated value.\
+    /// Takes exclusive write lock.
+    pub fn insert(&self, key: K, value: Rc<V>) -> Option<Rc<V>> {
+        self.map.write().insert(key, value)
+    }
+
+    /// Removes the entry from the cache.\
+    /// Returns associated value.\
+    /// Takes exclusive write lock.
+    pub fn remove<Q>(&self, key: &Q) -> Option<Rc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.write().remove(key)
+    }
+}
+
+impl<K, V, S> Default for ReadMostlyCache<K, V, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        ReadMostlyCache::with_hasher(S::default())
+    }
+}
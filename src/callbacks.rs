@@ -0,0 +1,165 @@
+use {
+    crate::{MaybeSend, MaybeSync, Rc},
+    alloc::vec::Vec,
+    core::fmt,
+};
+
+type Handler<E> = crate::maybe_box_fnmut!((&E));
+
+/// List of boxed event handlers.\
+/// Handlers are sendable when "sync" feature is enabled.
+///
+/// # Example
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use {maybe_sync::Callbacks, std::sync::{Arc, atomic::{AtomicU32, Ordering}}};
+/// let sum = Arc::new(AtomicU32::new(0));
+/// let captured = sum.clone();
+///
+/// let mut callbacks = Callbacks::new();
+/// callbacks.push(move |event: &u32| { captured.fetch_add(*event, Ordering::Relaxed); });
+///
+/// #[cfg(not(feature = "sync"))]
+/// {
+///     // Handlers may capture non-sendable values when "sync" feature is not enabled.
+///     let counter = std::rc::Rc::new(std::cell::Cell::new(0));
+///     callbacks.push(move |_: &u32| counter.set(counter.get() + 1));
+/// }
+///
+/// callbacks.dispatch(&1);
+/// callbacks.dispatch(&2);
+/// assert_eq!(sum.load(Ordering::Relaxed), 3);
+/// ```
+pub struct Callbacks<E> {
+    callbacks: Vec<Handler<E>>,
+}
+
+impl<E> Default for Callbacks<E> {
+    fn default() -> Self {
+        Callbacks::new()
+    }
+}
+
+impl<E> Callbacks<E> {
+    /// Creates new empty list of handlers.
+    pub fn new() -> Self {
+        Callbacks {
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Adds handler to the list.
+    pub fn push(&mut self, f: impl FnMut(&E) + MaybeSend + 'static) {
+        self.callbacks.push(alloc::boxed::Box::new(f));
+    }
+
+    /// Calls all handlers in order they were added.
+    pub fn dispatch(&mut self, event: &E) {
+        for f in &mut self.callbacks {
+            f(event);
+        }
+    }
+
+    /// Returns number of handlers in the list.
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Returns `true` if the list contains no handlers.
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+}
+
+/// Cheaply cloneable shared callback.
+///
+/// Callback is sendable and shareable when "sync" feature is enabled.\
+/// Two callbacks are equal if they share the same function.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::Callback;
+/// let double = Callback::from(|x: u32| x * 2);
+/// let copy = double.clone();
+/// assert_eq!(copy.emit(21), 42);
+/// assert_eq!(double, copy);
+/// assert_ne!(double, Callback::from(|x: u32| x * 2));
+///
+/// let noop = Callback::<u32>::noop();
+/// noop.emit(1);
+/// ```
+///
+/// Callback may capture non-sendable values only when "sync" feature is not enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::Callback, std::rc::Rc};
+/// let local = Rc::new(42);
+/// let callback = Callback::from(move |x: u32| x + *local);
+/// assert_eq!(callback.emit(1), 43);
+/// ```
+pub struct Callback<In, Out = ()> {
+    f: Rc<crate::dyn_maybe_send_sync!(Fn(In) -> Out)>,
+}
+
+impl<In, Out> Callback<In, Out> {
+    /// Calls the callback.
+    pub fn emit(&self, input: In) -> Out {
+        (self.f)(input)
+    }
+}
+
+impl<In, Out> Callback<In, Out>
+where
+    Out: Default,
+{
+    /// Returns callback that does nothing and returns default value.
+    pub fn noop() -> Self
+    where
+        In: 'static,
+        Out: 'static,
+    {
+        Callback::from(|_| Out::default())
+    }
+}
+
+impl<In, Out, F> From<F> for Callback<In, Out>
+where
+    F: Fn(In) -> Out + MaybeSend + MaybeSync + 'static,
+{
+    fn from(f: F) -> Self {
+        Callback { f: Rc::new(f) }
+    }
+}
+
+impl<In, Out> Default for Callback<In, Out>
+where
+    In: 'static,
+    Out: Default + 'static,
+{
+    fn default() -> Self {
+        Callback::noop()
+    }
+}
+
+impl<In, Out> Clone for Callback<In, Out> {
+    fn clone(&self) -> Self {
+        Callback { f: self.f.clone() }
+    }
+}
+
+impl<In, Out> PartialEq for Callback<In, Out> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.f, &other.f)
+    }
+}
+
+impl<In, Out> Eq for Callback<In, Out> {}
+
+impl<In, Out> fmt::Debug for Callback<In, Out> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "Callback({:p})", Rc::as_ptr(&self.f).cast::<()>())
+    }
+}
@@ -0,0 +1,1182 @@
+//! Helpers for [`BoxFuture`] that work the same way
+//! whether "sync" feature is enabled or not.
+//!
+//! [`BoxFuture`]: ../type.BoxFuture.html
+
+use {
+    crate::{BoxFuture, LocalBoxFuture, MaybeSend},
+    alloc::{boxed::Box, vec::Vec},
+    core::{
+        alloc::Layout,
+        fmt::{self, Debug},
+        future::Future,
+        mem,
+        pin::Pin,
+        ptr,
+        sync::atomic::{AtomicPtr, Ordering},
+        task::{Context, Poll, Waker},
+        time::Duration,
+    },
+};
+
+pub use crate::oneshot;
+
+pub mod mpsc;
+
+pub mod watch;
+
+/// Function that returns future which resolves after specified duration.
+///
+/// Host application provides one with [`set_timer`],
+/// e.g. wrapping `tokio::time::sleep` or `gloo_timers::future::sleep`.
+///
+/// [`set_timer`]: ./fn.set_timer.html
+pub type Timer = fn(Duration) -> BoxFuture<'static, ()>;
+
+static TIMER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets timer used by [`timeout`] and [`time::sleep`].
+///
+/// [`timeout`]: ./fn.timeout.html
+/// [`time::sleep`]: ../time/fn.sleep.html
+pub fn set_timer(timer: Timer) {
+    TIMER.store(timer as *mut (), Ordering::Release);
+}
+
+pub(crate) fn timer() -> Option<Timer> {
+    let ptr = TIMER.load(Ordering::Acquire);
+    if ptr.is_null() {
+        None
+    } else {
+        // Safety: only `Timer` function pointers are stored in `TIMER`.
+        Some(unsafe { core::mem::transmute::<*mut (), Timer>(ptr) })
+    }
+}
+
+/// Polls the future once and returns its output if it is immediately ready.\
+/// Returns `None` if the future is pending. The future is dropped in this case.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::future::now_or_never;
+/// assert_eq!(now_or_never(Box::pin(async { 42 })), Some(42));
+/// assert_eq!(now_or_never(Box::pin(std::future::pending::<u32>())), None);
+/// ```
+pub fn now_or_never<T>(mut f: BoxFuture<'_, T>) -> Option<T> {
+    let mut cx = Context::from_waker(Waker::noop());
+    match f.as_mut().poll(&mut cx) {
+        Poll::Ready(value) => Some(value),
+        Poll::Pending => None,
+    }
+}
+
+/// Boxes the future into [`BoxFuture`].
+///
+/// Unlike coercion of `Box::pin` result this function requires
+/// the future to satisfy [`MaybeSend`] bound at call site,
+/// so the error points to the offending future
+/// when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{future::{boxed, now_or_never}, BoxFuture};
+/// trait Loader {
+///     fn load(&self, id: u32) -> BoxFuture<'_, u32>;
+/// }
+///
+/// struct Immediate;
+///
+/// impl Loader for Immediate {
+///     fn load(&self, id: u32) -> BoxFuture<'_, u32> {
+///         boxed(async move { id })
+///     }
+/// }
+///
+/// assert_eq!(now_or_never(Immediate.load(42)), Some(42));
+/// ```
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::future::{boxed, now_or_never}, std::rc::Rc};
+/// let cache = Rc::new(42);
+/// let f = boxed(async move { *cache });
+/// assert_eq!(now_or_never(f), Some(42));
+/// ```
+///
+/// [`BoxFuture`]: ../type.BoxFuture.html
+/// [`MaybeSend`]: ../trait.MaybeSend.html
+pub fn boxed<'a, F>(f: F) -> BoxFuture<'a, F::Output>
+where
+    F: Future + MaybeSend + 'a,
+{
+    Box::pin(f)
+}
+
+/// Converts [`BoxFuture`] into [`LocalBoxFuture`].\
+/// This is no-op when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{future::into_local, BoxFuture, LocalBoxFuture}, std::task::{Context, Poll, Waker}};
+/// fn run_on_main_thread(mut f: LocalBoxFuture<'_, u32>) -> Option<u32> {
+///     let mut cx = Context::from_waker(Waker::noop());
+///     match f.as_mut().poll(&mut cx) {
+///         Poll::Ready(value) => Some(value),
+///         Poll::Pending => None,
+///     }
+/// }
+///
+/// let f: BoxFuture<'_, u32> = Box::pin(async { 42 });
+/// assert_eq!(run_on_main_thread(into_local(f)), Some(42));
+/// ```
+///
+/// [`BoxFuture`]: ../type.BoxFuture.html
+/// [`LocalBoxFuture`]: ../type.LocalBoxFuture.html
+pub fn into_local<T>(f: BoxFuture<'_, T>) -> LocalBoxFuture<'_, T> {
+    f
+}
+
+/// Returns boxed future that is immediately ready with the value.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::future::{now_or_never, ready};
+/// assert_eq!(now_or_never(ready(42)), Some(42));
+/// ```
+pub fn ready<T>(value: T) -> BoxFuture<'static, T>
+where
+    T: MaybeSend + 'static,
+{
+    Box::pin(core::future::ready(value))
+}
+
+/// Returns boxed future that never completes.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::future::{now_or_never, pending};
+/// assert_eq!(now_or_never(pending::<u32>()), None);
+/// ```
+pub fn pending<T>() -> BoxFuture<'static, T>
+where
+    T: MaybeSend + 'static,
+{
+    Box::pin(core::future::pending())
+}
+
+/// Maps error of the boxed fallible future with provided function.
+///
+/// Resulting future is sendable when "sync" feature is enabled,
+/// as both the future and the function are [`MaybeSend`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{future::{map_err, now_or_never}, BoxFuture};
+/// #[derive(Debug, PartialEq)]
+/// enum LoadError {
+///     Parse(std::num::ParseIntError),
+/// }
+///
+/// fn parse(s: &str) -> BoxFuture<'_, Result<u32, std::num::ParseIntError>> {
+///     Box::pin(async move { s.parse() })
+/// }
+///
+/// fn load(s: &str) -> BoxFuture<'_, Result<u32, LoadError>> {
+///     map_err(parse(s), LoadError::Parse)
+/// }
+///
+/// assert_eq!(now_or_never(load("42")), Some(Ok(42)));
+/// assert!(matches!(now_or_never(load("x")), Some(Err(LoadError::Parse(_)))));
+/// ```
+///
+/// [`MaybeSend`]: ../trait.MaybeSend.html
+pub fn map_err<'a, T, E, E2>(
+    f: BoxFuture<'a, Result<T, E>>,
+    g: impl FnOnce(E) -> E2 + MaybeSend + 'a,
+) -> BoxFuture<'a, Result<T, E2>>
+where
+    T: 'a,
+    E: 'a,
+    E2: 'a,
+{
+    Box::pin(async move { f.await.map_err(g) })
+}
+
+/// Retries fallible future produced by `make_future`
+/// until it succeeds or `attempts` are exhausted.\
+/// Resolves to the first success or the last error.
+/// At least one attempt is made.
+///
+/// Resulting future is sendable when "sync" feature is enabled,
+/// as both the futures and the function are [`MaybeSend`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{future::{now_or_never, retry}, BoxFuture};
+/// fn fetch(attempt: u32) -> BoxFuture<'static, Result<&'static str, u32>> {
+///     Box::pin(async move { if attempt < 3 { Err(attempt) } else { Ok("data") } })
+/// }
+///
+/// let mut attempt = 0;
+/// let data = retry(5, || {
+///     attempt += 1;
+///     fetch(attempt)
+/// });
+/// assert_eq!(now_or_never(data), Some(Ok("data")));
+/// assert_eq!(attempt, 3);
+///
+/// let mut attempt = 0;
+/// let data = retry(2, || {
+///     attempt += 1;
+///     fetch(attempt)
+/// });
+/// assert_eq!(now_or_never(data), Some(Err(2)));
+/// ```
+///
+/// [`MaybeSend`]: ../trait.MaybeSend.html
+pub fn retry<'a, T, E>(
+    attempts: usize,
+    make_future: impl FnMut() -> BoxFuture<'a, Result<T, E>> + MaybeSend + 'a,
+) -> BoxFuture<'a, Result<T, E>>
+where
+    T: 'a,
+    E: 'a,
+{
+    retry_with(attempts, |_| Duration::ZERO, make_future)
+}
+
+/// Same as [`retry`], but waits between attempts.\
+/// `backoff` receives number of failed attempts so far
+/// and returns delay before the next one.
+/// Delays are awaited with [`time::sleep`], zero delays are skipped.
+///
+/// # Panics
+///
+/// Resulting future panics if non-zero delay is requested
+/// while no timer was set with [`set_timer`]
+/// and neither "tokio" nor "gloo-timers" feature is enabled,
+/// or if [`TokioDelay`] fallback is used outside of tokio runtime.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{future::{now_or_never, retry_with, set_timer}, BoxFuture}, std::time::Duration};
+/// // Timer that is elapsed immediately.
+/// set_timer(|_| Box::pin(async {}));
+///
+/// fn fetch(attempt: u32) -> BoxFuture<'static, Result<&'static str, u32>> {
+///     Box::pin(async move { if attempt < 3 { Err(attempt) } else { Ok("data") } })
+/// }
+///
+/// let mut delays = Vec::new();
+/// let mut attempt = 0;
+/// let data = retry_with(
+///     5,
+///     |failed| {
+///         let delay = Duration::from_millis(100) * 2u32.pow(failed as u32 - 1);
+///         delays.push(delay);
+///         delay
+///     },
+///     || {
+///         attempt += 1;
+///         fetch(attempt)
+///     },
+/// );
+/// assert_eq!(now_or_never(data), Some(Ok("data")));
+/// assert_eq!(delays, [Duration::from_millis(100), Duration::from_millis(200)]);
+/// ```
+///
+/// [`retry`]: ./fn.retry.html
+/// [`time::sleep`]: ../time/fn.sleep.html
+/// [`set_timer`]: ./fn.set_timer.html
+/// [`TokioDelay`]: ./struct.TokioDelay.html
+pub fn retry_with<'a, T, E>(
+    attempts: usize,
+    mut backoff: impl FnMut(usize) -> Duration + MaybeSend + 'a,
+    mut make_future: impl FnMut() -> BoxFuture<'a, Result<T, E>> + MaybeSend + 'a,
+) -> BoxFuture<'a, Result<T, E>>
+where
+    T: 'a,
+    E: 'a,
+{
+    Box::pin(async move {
+        let mut failed = 0;
+        loop {
+            match make_future().await {
+                Ok(value) => return Ok(value),
+                Err(err) if failed + 1 >= attempts => return Err(err),
+                // Error is dropped before waiting, so it needs not be sendable.
+                Err(_) => failed += 1,
+            }
+            let delay = backoff(failed);
+            if delay > Duration::ZERO {
+                crate::time::sleep(delay).await;
+            }
+        }
+    })
+}
+
+/// Wraps the future to resolve to `None` if it doesn't complete
+/// within specified duration.
+///
+/// # Panics
+///
+/// This function panics if timer was not set with [`set_timer`].
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::future::{now_or_never, set_timer, timeout}, std::time::Duration};
+/// // Timer that is elapsed immediately.
+/// set_timer(|_| Box::pin(async {}));
+///
+/// let ready = timeout(Duration::from_secs(1), Box::pin(async { 42 }));
+/// assert_eq!(now_or_never(ready), Some(Some(42)));
+///
+/// let pending = timeout(Duration::from_secs(1), Box::pin(std::future::pending::<u32>()));
+/// assert_eq!(now_or_never(pending), Some(None));
+/// ```
+///
+/// [`set_timer`]: ./fn.set_timer.html
+pub fn timeout<'a, T>(dur: Duration, f: BoxFuture<'a, T>) -> BoxFuture<'a, Option<T>>
+where
+    T: 'a,
+{
+    let timer = timer().expect("Timer must be set with `maybe_sync::future::set_timer`");
+    let timeout = Timeout {
+        future: Some(f),
+        delay: timer(dur),
+    };
+    Box::pin(async move { timeout.await.ok() })
+}
+
+/// Source of delay futures for [`timeout_with`].
+///
+/// Implemented for [`Timer`] functions,
+/// and for [`TokioDelay`] and [`GlooDelay`] with corresponding features.
+///
+/// [`timeout_with`]: ./fn.timeout_with.html
+/// [`Timer`]: ./type.Timer.html
+/// [`TokioDelay`]: ./struct.TokioDelay.html
+/// [`GlooDelay`]: ./struct.GlooDelay.html
+pub trait Delay {
+    /// Returns future which resolves after specified duration.
+    fn delay(&self, dur: Duration) -> BoxFuture<'static, ()>;
+}
+
+impl Delay for Timer {
+    fn delay(&self, dur: Duration) -> BoxFuture<'static, ()> {
+        self(dur)
+    }
+}
+
+/// [`Delay`] implementation using `tokio::time::sleep`.
+///
+/// Must be used within tokio runtime with time driver enabled.
+///
+/// [`Delay`]: ./trait.Delay.html
+#[cfg(feature = "tokio")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "tokio")))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioDelay;
+
+#[cfg(feature = "tokio")]
+impl Delay for TokioDelay {
+    fn delay(&self, dur: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(dur))
+    }
+}
+
+/// [`Delay`] implementation using `gloo_timers::future::sleep`.
+///
+/// Available only when "sync" feature is not enabled,
+/// as browser timers are not sendable.
+///
+/// [`Delay`]: ./trait.Delay.html
+#[cfg(all(feature = "gloo-timers", not(feature = "sync")))]
+#[cfg_attr(
+    all(doc, feature = "unstable-doc"),
+    doc(cfg(all(feature = "gloo-timers", not(feature = "sync"))))
+)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GlooDelay;
+
+#[cfg(all(feature = "gloo-timers", not(feature = "sync")))]
+impl Delay for GlooDelay {
+    fn delay(&self, dur: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(gloo_timers::future::sleep(dur))
+    }
+}
+
+/// Error returned by [`timeout_with`] when the future
+/// doesn't complete within specified duration.
+///
+/// [`timeout_with`]: ./fn.timeout_with.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl core::error::Error for Elapsed {}
+
+/// Wraps the future to resolve to [`Elapsed`] error if it doesn't complete
+/// within specified duration, using provided [`Delay`].\
+/// The future is dropped as soon as the duration elapses.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::future::{now_or_never, timeout_with, Elapsed, Timer};
+/// # use std::time::Duration;
+/// // Delay that is elapsed immediately.
+/// let delay: Timer = |_| Box::pin(async {});
+///
+/// let ready = timeout_with(&delay, Duration::from_secs(1), async { 42 });
+/// assert_eq!(now_or_never(ready), Some(Ok(42)));
+///
+/// let pending = timeout_with(&delay, Duration::from_secs(1), std::future::pending::<u32>());
+/// assert_eq!(now_or_never(pending), Some(Err(Elapsed)));
+/// ```
+///
+/// [`Elapsed`]: ./struct.Elapsed.html
+/// [`Delay`]: ./trait.Delay.html
+pub fn timeout_with<'a, D, F>(
+    delay: &D,
+    dur: Duration,
+    future: F,
+) -> BoxFuture<'a, Result<F::Output, Elapsed>>
+where
+    D: Delay + ?Sized,
+    F: Future + MaybeSend + 'a,
+    F::Output: 'a,
+{
+    Box::pin(Timeout {
+        future: Some(Box::pin(future)),
+        delay: delay.delay(dur),
+    })
+}
+
+struct Timeout<'a, T> {
+    future: Option<BoxFuture<'a, T>>,
+    delay: BoxFuture<'static, ()>,
+}
+
+impl<T> Future for Timeout<'_, T> {
+    type Output = Result<T, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, Elapsed>> {
+        let me = self.get_mut();
+        let future = match &mut me.future {
+            Some(future) => future,
+            None => return Poll::Ready(Err(Elapsed)),
+        };
+        if let Poll::Ready(value) = future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(value));
+        }
+        match me.delay.as_mut().poll(cx) {
+            Poll::Ready(()) => {
+                me.future = None;
+                Poll::Ready(Err(Elapsed))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Value produced by one of two alternatives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Either<A, B> {
+    /// Value produced by the first alternative.
+    Left(A),
+    /// Value produced by the second alternative.
+    Right(B),
+}
+
+/// Waits for whichever of two futures completes first.\
+/// Futures are polled in order, so `a` wins if both are ready.
+///
+/// This is the portable way to multiplex receivers from [`oneshot`],
+/// [`mpsc`] and [`watch`] with a single code path
+/// whether "sync" feature is enabled or not.\
+/// Pass futures by mutable reference to keep them alive
+/// and poll them again on the next iteration.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::future::{mpsc, oneshot, select2, Either}, std::{future::Future, pin::pin, task::{Context, Poll, Waker}}};
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let (tx, mut input) = mpsc::unbounded();
+/// let (stop, mut shutdown) = oneshot::channel::<()>();
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+///
+/// let mut stop = Some(stop);
+/// let mut received = Vec::new();
+/// loop {
+///     match pin!(select2(&mut shutdown, input.recv())).poll(&mut cx) {
+///         Poll::Ready(Either::Left(_)) => break,
+///         Poll::Ready(Either::Right(Some(value))) => received.push(value),
+///         Poll::Ready(Either::Right(None)) => unreachable!(),
+///         Poll::Pending => stop.take().unwrap().send(()).unwrap(),
+///     }
+/// }
+/// assert_eq!(received, [1, 2]);
+/// ```
+///
+/// [`oneshot`]: ./oneshot/index.html
+/// [`mpsc`]: ./mpsc/index.html
+/// [`watch`]: ./watch/index.html
+pub fn select2<A, B>(a: A, b: B) -> Select2<A, B>
+where
+    A: Future + Unpin,
+    B: Future + Unpin,
+{
+    Select2 { a, b }
+}
+
+/// Future returned by [`select2`].
+///
+/// [`select2`]: ./fn.select2.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct Select2<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Future for Select2<A, B>
+where
+    A: Future + Unpin,
+    B: Future + Unpin,
+{
+    type Output = Either<A::Output, B::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        if let Poll::Ready(value) = Pin::new(&mut me.a).poll(cx) {
+            return Poll::Ready(Either::Left(value));
+        }
+        if let Poll::Ready(value) = Pin::new(&mut me.b).poll(cx) {
+            return Poll::Ready(Either::Right(value));
+        }
+        Poll::Pending
+    }
+}
+
+/// Boxed future that reuses its allocation when replaced
+/// with a future of the same layout.
+///
+/// Sendable when "sync" feature is enabled, like [`BoxFuture`].
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::future::ReusableBoxFuture, std::{future::Future, pin::Pin, task::{Context, Poll, Waker}}};
+/// async fn tick(n: u32) -> u32 {
+///     n + 1
+/// }
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let mut future = ReusableBoxFuture::new(tick(0));
+/// assert_eq!(future.poll(&mut cx), Poll::Ready(1));
+///
+/// // Same future type reuses the allocation.
+/// assert!(future.try_set(tick(1)).is_ok());
+/// assert_eq!(future.poll(&mut cx), Poll::Ready(2));
+///
+/// // Different layout doesn't fit and `set` allocates anew.
+/// assert!(future.try_set(std::future::pending()).is_err());
+/// future.set(std::future::ready(42));
+/// assert_eq!(Pin::new(&mut future).poll(&mut cx), Poll::Ready(42));
+/// ```
+///
+/// [`BoxFuture`]: ../type.BoxFuture.html
+pub struct ReusableBoxFuture<'a, T> {
+    boxed: BoxFuture<'a, T>,
+}
+
+impl<'a, T> ReusableBoxFuture<'a, T>
+where
+    T: 'a,
+{
+    /// Boxes the future.
+    pub fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = T> + MaybeSend + 'a,
+    {
+        ReusableBoxFuture {
+            boxed: Box::pin(future),
+        }
+    }
+
+    /// Replaces the future.\
+    /// Reuses the allocation if layout of the new future matches
+    /// layout of the current one, otherwise allocates anew.
+    pub fn set<F>(&mut self, future: F)
+    where
+        F: Future<Output = T> + MaybeSend + 'a,
+    {
+        if let Err(future) = self.try_set(future) {
+            self.boxed = Box::pin(future);
+        }
+    }
+
+    /// Replaces the future reusing the allocation.\
+    /// Returns the new future back if its layout doesn't match
+    /// layout of the current one.
+    pub fn try_set<F>(&mut self, future: F) -> Result<(), F>
+    where
+        F: Future<Output = T> + MaybeSend + 'a,
+    {
+        if Layout::for_value::<dyn Future<Output = T>>(&*self.boxed) != Layout::new::<F>() {
+            return Err(future);
+        }
+
+        // `Pending` is zero-sized, so this does not allocate.
+        let boxed = mem::replace(&mut self.boxed, Box::pin(core::future::pending()));
+
+        // Safety: old future is never moved, it is dropped in place.
+        let raw = Box::into_raw(unsafe { Pin::into_inner_unchecked(boxed) });
+
+        // New future must be written and boxed even if old one panics on drop.
+        let guard = WriteOnDrop {
+            raw: raw.cast::<F>(),
+            future: Some(future),
+            slot: &mut self.boxed,
+        };
+
+        // Safety: `raw` points to valid future that is dropped only once.
+        unsafe { ptr::drop_in_place(raw) };
+        drop(guard);
+        Ok(())
+    }
+
+    /// Polls the future.
+    pub fn poll(&mut self, cx: &mut Context<'_>) -> Poll<T> {
+        self.boxed.as_mut().poll(cx)
+    }
+}
+
+impl<T> Future for ReusableBoxFuture<'_, T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.get_mut().boxed.as_mut().poll(cx)
+    }
+}
+
+impl<T> Debug for ReusableBoxFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReusableBoxFuture").finish()
+    }
+}
+
+struct WriteOnDrop<'a, 'b, F, T>
+where
+    F: Future<Output = T> + MaybeSend + 'a,
+{
+    raw: *mut F,
+    future: Option<F>,
+    slot: &'b mut BoxFuture<'a, T>,
+}
+
+impl<'a, F, T> Drop for WriteOnDrop<'a, '_, F, T>
+where
+    F: Future<Output = T> + MaybeSend + 'a,
+{
+    fn drop(&mut self) {
+        if let Some(future) = self.future.take() {
+            // Safety: `raw` comes from `Box` with the same layout as `F`
+            // and its previous value was dropped.
+            *self.slot = unsafe {
+                self.raw.write(future);
+                Pin::new_unchecked(Box::from_raw(self.raw))
+            };
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+type RemoteOutput<T> = std::thread::Result<T>;
+
+#[cfg(not(feature = "std"))]
+type RemoteOutput<T> = T;
+
+/// Splits the future into [`Remote`] future that can be spawned
+/// and [`RemoteHandle`] that resolves to its output.
+///
+/// Dropping [`RemoteHandle`] cancels [`Remote`] future at its next poll.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::future::{now_or_never, remote_handle};
+/// let (remote, handle) = remote_handle(async { 42 });
+///
+/// // Spawn `remote` on executor of choice.
+/// assert_eq!(now_or_never(Box::pin(remote)), Some(()));
+///
+/// assert_eq!(now_or_never(Box::pin(handle)), Some(42));
+/// ```
+///
+/// [`Remote`]: ./struct.Remote.html
+/// [`RemoteHandle`]: ./struct.RemoteHandle.html
+pub fn remote_handle<F>(future: F) -> (Remote<F>, RemoteHandle<F::Output>)
+where
+    F: Future,
+{
+    let (tx, rx) = crate::oneshot::new();
+    (
+        Remote {
+            tx: Some(tx),
+            future,
+        },
+        RemoteHandle { rx },
+    )
+}
+
+/// Future that drives wrapped future and sends its output to [`RemoteHandle`].\
+/// Created by [`remote_handle`] function.
+///
+/// When "std" feature is enabled, panic in the wrapped future
+/// is caught and resumed when [`RemoteHandle`] is polled.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::future::{pending, remote_handle}, std::{future::Future, task::{Context, Poll, Waker}}};
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let (remote, handle) = remote_handle(pending::<u32>());
+/// let mut remote = Box::pin(remote);
+/// assert_eq!(remote.as_mut().poll(&mut cx), Poll::Pending);
+///
+/// // Remote future completes on next poll after handle is dropped.
+/// drop(handle);
+/// assert_eq!(remote.as_mut().poll(&mut cx), Poll::Ready(()));
+/// ```
+///
+/// [`remote_handle`]: ./fn.remote_handle.html
+/// [`RemoteHandle`]: ./struct.RemoteHandle.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Remote<F>
+where
+    F: Future,
+{
+    tx: Option<crate::oneshot::Sender<RemoteOutput<F::Output>>>,
+    future: F,
+}
+
+impl<F> Future for Remote<F>
+where
+    F: Future,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Safety: only `future` is structurally pinned.
+        let me = unsafe { self.get_unchecked_mut() };
+
+        if me.tx.as_ref().is_none_or(|tx| tx.is_canceled()) {
+            return Poll::Ready(());
+        }
+
+        // Safety: `future` is never moved out of pinned `self`.
+        let future = unsafe { Pin::new_unchecked(&mut me.future) };
+
+        #[cfg(feature = "std")]
+        let output = {
+            let poll = std::panic::AssertUnwindSafe(|| future.poll(cx));
+            match std::panic::catch_unwind(poll) {
+                Ok(Poll::Pending) => return Poll::Pending,
+                Ok(Poll::Ready(output)) => Ok(output),
+                Err(panic) => Err(panic),
+            }
+        };
+
+        #[cfg(not(feature = "std"))]
+        let output = match future.poll(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(output) => output,
+        };
+
+        if let Some(tx) = me.tx.take() {
+            // Handle may be dropped concurrently.
+            let _ = tx.send(output);
+        }
+        Poll::Ready(())
+    }
+}
+
+impl<F> Debug for Remote<F>
+where
+    F: Future + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Remote")
+            .field("future", &self.future)
+            .finish()
+    }
+}
+
+/// Handle to the output of [`Remote`] future.\
+/// Created by [`remote_handle`] function.
+///
+/// Resolves to the output of the remote future.
+/// Dropping this handle cancels [`Remote`] future at its next poll.
+///
+/// # Panics
+///
+/// Polling this handle panics if [`Remote`] future was dropped before completion.\
+/// When "std" feature is enabled, panic in the remote future
+/// is resumed when this handle is polled.
+///
+/// ```
+/// # use maybe_sync::future::{now_or_never, remote_handle};
+/// #[cfg(feature = "std")]
+/// {
+///     let (remote, handle) = remote_handle(async { panic!("boom") });
+///     assert_eq!(now_or_never(Box::pin(remote)), Some(()));
+///
+///     let handle = std::panic::AssertUnwindSafe(handle);
+///     let result = std::panic::catch_unwind(move || now_or_never(Box::pin(handle.0)));
+///     let panic = result.unwrap_err();
+///     assert_eq!(panic.downcast_ref::<&str>(), Some(&"boom"));
+/// }
+/// ```
+///
+/// [`remote_handle`]: ./fn.remote_handle.html
+/// [`Remote`]: ./struct.Remote.html
+#[must_use = "dropping a remote handle cancels the underlying future"]
+pub struct RemoteHandle<T> {
+    rx: crate::oneshot::Receiver<RemoteOutput<T>>,
+}
+
+impl<T> Future for RemoteHandle<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        match Pin::new(&mut self.rx).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            #[cfg(feature = "std")]
+            Poll::Ready(Ok(Ok(output))) => Poll::Ready(output),
+            #[cfg(feature = "std")]
+            Poll::Ready(Ok(Err(panic))) => std::panic::resume_unwind(panic),
+            #[cfg(not(feature = "std"))]
+            Poll::Ready(Ok(output)) => Poll::Ready(output),
+            Poll::Ready(Err(crate::oneshot::Canceled)) => {
+                panic!("Remote future was dropped before completion")
+            }
+        }
+    }
+}
+
+impl<T> Debug for RemoteHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteHandle").finish()
+    }
+}
+
+/// Cloneable future that resolves each clone to the output of the wrapped future.
+///
+/// Wrapped future is polled by whichever clone is polled,
+/// with a waker that wakes every clone waiting for it,
+/// and its output is cloned for every clone.\
+/// Sendable when "sync" feature is enabled and `T: Send`.
+///
+/// # Panics
+///
+/// Polling any clone panics if the wrapped future panicked.
+///
+/// # Example
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use {maybe_sync::{future::Shared, oneshot}, std::{future::Future, pin::Pin, task::{Context, Poll, Waker}}};
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let (tx, rx) = oneshot::channel();
+/// let mut a = Shared::new(rx);
+/// let mut b = a.clone();
+///
+/// assert_eq!(Pin::new(&mut a).poll(&mut cx), Poll::Pending);
+/// assert_eq!(Pin::new(&mut b).poll(&mut cx), Poll::Pending);
+///
+/// tx.send(42).unwrap();
+/// assert_eq!(Pin::new(&mut b).poll(&mut cx), Poll::Ready(Ok(42)));
+/// assert_eq!(Pin::new(&mut a).poll(&mut cx), Poll::Ready(Ok(42)));
+/// ```
+///
+/// Every clone is woken, not only the one that polled the wrapped future last.
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use {maybe_sync::{future::Shared, oneshot}, std::{future::Future, pin::Pin, sync::{atomic::{AtomicUsize, Ordering}, Arc}, task::{Context, Poll, Wake, Waker}}};
+/// struct Counter(AtomicUsize);
+///
+/// impl Wake for Counter {
+///     fn wake(self: Arc<Self>) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let (tx, rx) = oneshot::channel::<u32>();
+/// let mut a = Shared::new(rx);
+/// let mut b = a.clone();
+///
+/// let (wakes_a, wakes_b) = (Arc::new(Counter(AtomicUsize::new(0))), Arc::new(Counter(AtomicUsize::new(0))));
+/// let waker_a = Waker::from(wakes_a.clone());
+/// let waker_b = Waker::from(wakes_b.clone());
+///
+/// // Both clones poll the wrapped future.
+/// assert!(Pin::new(&mut a).poll(&mut Context::from_waker(&waker_a)).is_pending());
+/// assert!(Pin::new(&mut b).poll(&mut Context::from_waker(&waker_b)).is_pending());
+///
+/// tx.send(42).unwrap();
+/// assert_eq!(wakes_a.0.load(Ordering::Relaxed), 1);
+/// assert_eq!(wakes_b.0.load(Ordering::Relaxed), 1);
+/// ```
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Shared<T> {
+    inner: crate::Rc<crate::Mutex<SharedState<T>>>,
+    notifier: alloc::sync::Arc<Notifier>,
+    waker_key: Option<usize>,
+}
+
+enum SharedState<T> {
+    Idle(BoxFuture<'static, T>),
+    Polling,
+    Done(T),
+    Poisoned,
+}
+
+/// Slab of wakers registered by clones of [`Shared`].
+///
+/// Wakers must be `Send + Sync` even when "sync" feature is disabled,
+/// so the slab is guarded by a spin lock instead of crate's `Mutex`.
+struct Notifier {
+    locked: core::sync::atomic::AtomicBool,
+    slab: core::cell::UnsafeCell<WakerSlab>,
+}
+
+#[derive(Default)]
+struct WakerSlab {
+    wakers: Vec<Option<Waker>>,
+    vacant: Vec<usize>,
+}
+
+// Safety: `slab` is accessed only while `locked` is held, and `Waker` is `Send + Sync`.
+unsafe impl Send for Notifier {}
+unsafe impl Sync for Notifier {}
+
+struct Unlock<'a>(&'a core::sync::atomic::AtomicBool);
+
+impl Drop for Unlock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl Notifier {
+    fn with<R>(&self, f: impl FnOnce(&mut WakerSlab) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        let _unlock = Unlock(&self.locked);
+        // Safety: the lock is held until `_unlock` is dropped.
+        f(unsafe { &mut *self.slab.get() })
+    }
+
+    fn register(&self, key: &mut Option<usize>, waker: &Waker) {
+        self.with(|slab| match *key {
+            Some(key) => match &mut slab.wakers[key] {
+                Some(w) if w.will_wake(waker) => {}
+                slot => *slot = Some(waker.clone()),
+            },
+            None => match slab.vacant.pop() {
+                Some(vacant) => {
+                    slab.wakers[vacant] = Some(waker.clone());
+                    *key = Some(vacant);
+                }
+                None => {
+                    slab.wakers.push(Some(waker.clone()));
+                    *key = Some(slab.wakers.len() - 1);
+                }
+            },
+        })
+    }
+
+    fn unregister(&self, key: usize) {
+        let waker = self.with(|slab| {
+            slab.vacant.push(key);
+            slab.wakers[key].take()
+        });
+        drop(waker);
+    }
+
+    fn wake_all(&self) {
+        // Wake outside of the lock, as wakers may poll clones immediately.
+        let wakers: Vec<Waker> =
+            self.with(|slab| slab.wakers.iter_mut().filter_map(Option::take).collect());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl alloc::task::Wake for Notifier {
+    fn wake(self: alloc::sync::Arc<Self>) {
+        self.wake_all()
+    }
+
+    fn wake_by_ref(self: &alloc::sync::Arc<Self>) {
+        self.wake_all()
+    }
+}
+
+impl<T> Shared<T>
+where
+    T: Clone,
+{
+    /// Wraps the future to be awaited from multiple places.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+    #[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+    /// # use maybe_sync::future::{now_or_never, Shared};
+    /// maybe_sync::send_ok::<Shared<u32>>();
+    ///
+    /// let a = Shared::new(async { vec![1, 2, 3] });
+    /// let b = a.clone();
+    /// let both = async move { (a.await, b.await) };
+    /// assert_eq!(now_or_never(Box::pin(both)), Some((vec![1, 2, 3], vec![1, 2, 3])));
+    /// ```
+    pub fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = T> + MaybeSend + 'static,
+    {
+        Shared {
+            inner: crate::Rc::new(crate::Mutex::new(SharedState::Idle(Box::pin(future)))),
+            notifier: alloc::sync::Arc::new(Notifier {
+                locked: core::sync::atomic::AtomicBool::new(false),
+                slab: Default::default(),
+            }),
+            waker_key: None,
+        }
+    }
+
+    /// Returns the output if the wrapped future has completed.
+    pub fn peek(&self) -> Option<T> {
+        match &*self.inner.lock() {
+            SharedState::Done(output) => Some(output.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared {
+            inner: self.inner.clone(),
+            notifier: self.notifier.clone(),
+            waker_key: None,
+        }
+    }
+}
+
+impl<T> Future for Shared<T>
+where
+    T: Clone,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let this = self.get_mut();
+        let mut inner = this.inner.lock();
+        let mut future = match &*inner {
+            SharedState::Done(output) => return Poll::Ready(output.clone()),
+            SharedState::Poisoned => panic!("Shared future panicked"),
+            SharedState::Polling => {
+                this.notifier.register(&mut this.waker_key, cx.waker());
+                return Poll::Pending;
+            }
+            SharedState::Idle(_) => match mem::replace(&mut *inner, SharedState::Polling) {
+                SharedState::Idle(future) => future,
+                _ => unreachable!(),
+            },
+        };
+        this.notifier.register(&mut this.waker_key, cx.waker());
+
+        // Poll without holding the lock, as the future may wake clones immediately.
+        drop(inner);
+        let waker = Waker::from(this.notifier.clone());
+        let guard = PoisonOnUnwind {
+            inner: &this.inner,
+            notifier: &this.notifier,
+        };
+        let poll = future.as_mut().poll(&mut Context::from_waker(&waker));
+        mem::forget(guard);
+
+        let mut inner = this.inner.lock();
+        match poll {
+            Poll::Pending => {
+                *inner = SharedState::Idle(future);
+                Poll::Pending
+            }
+            Poll::Ready(output) => {
+                *inner = SharedState::Done(output.clone());
+                drop(inner);
+
+                if let Some(key) = this.waker_key.take() {
+                    this.notifier.unregister(key);
+                }
+                this.notifier.wake_all();
+                Poll::Ready(output)
+            }
+        }
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        // Wrapped future holds waker for all clones,
+        // so remaining ones are woken to take over polling it.
+        if let Some(key) = self.waker_key {
+            self.notifier.unregister(key);
+        }
+    }
+}
+
+impl<T> Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let done = matches!(*self.inner.lock(), SharedState::Done(_));
+        f.debug_struct("Shared").field("done", &done).finish()
+    }
+}
+
+struct PoisonOnUnwind<'a, T> {
+    inner: &'a crate::Rc<crate::Mutex<SharedState<T>>>,
+    notifier: &'a Notifier,
+}
+
+impl<T> Drop for PoisonOnUnwind<'_, T> {
+    fn drop(&mut self) {
+        *self.inner.lock() = SharedState::Poisoned;
+        self.notifier.wake_all();
+    }
+}
@@ -0,0 +1,804 @@
+//! Multi-producer single-consumer channels for async code.
+//!
+//! [`channel`] creates bounded channel where
+//! [`Sender::send`] waits while the channel is full.
+//! Pending senders are served in FIFO order.
+//! [`unbounded`] creates channel where sending never waits.
+//! Both work the same way on multithreaded and local executors.
+//!
+//! Senders and receivers are sendable when "sync" feature is enabled
+//! and `T: Send`.\
+//! With "futures" feature [`Receiver`] and [`UnboundedReceiver`] implement `Stream`
+//! and [`Sender`] implements `Sink`.
+//!
+//! # Example
+//!
+//! ```
+//! # use maybe_sync::future::{mpsc, now_or_never};
+//! let (tx, mut rx) = mpsc::channel(1);
+//! let pipeline = async move {
+//!     tx.send(1).await.unwrap();
+//!     let first = rx.recv().await;
+//!     tx.send(2).await.unwrap();
+//!     drop(tx);
+//!     (first, rx.recv().await, rx.recv().await)
+//! };
+//! assert_eq!(now_or_never(Box::pin(pipeline)), Some((Some(1), Some(2), None)));
+//! ```
+//!
+//! [`channel`]: ./fn.channel.html
+//! [`unbounded`]: ./fn.unbounded.html
+//! [`Sender`]: ./struct.Sender.html
+//! [`UnboundedReceiver`]: ./struct.UnboundedReceiver.html
+//! [`Sender::send`]: ./struct.Sender.html#method.send
+//! [`Receiver`]: ./struct.Receiver.html
+
+use {
+    crate::{
+        mpsc::{SendError, TryRecvError, TrySendError},
+        MaybeSend, Mutex, Rc,
+    },
+    alloc::collections::VecDeque,
+    core::{
+        fmt::{self, Debug},
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, Waker},
+    },
+};
+
+struct State<T> {
+    queue: VecDeque<T>,
+    cap: usize,
+    // Slots reserved with `Sink::poll_ready`.
+    reserved: usize,
+    senders: usize,
+    closed: bool,
+    receiver: Option<Waker>,
+    // Pending senders in FIFO order.
+    waiters: VecDeque<(u64, Waker)>,
+    next_ticket: u64,
+}
+
+impl<T> State<T> {
+    fn has_space(&self) -> bool {
+        self.queue.len() + self.reserved < self.cap
+    }
+
+    /// Returns waker of the first pending sender if it can proceed.
+    fn front_waker(&self) -> Option<Waker> {
+        if self.has_space() {
+            self.waiters.front().map(|(_, waker)| waker.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Checks if sender holding `ticket` may take a slot now.
+    /// Returns `Ready(false)` if the channel is closed.
+    fn poll_turn(&mut self, ticket: &mut Option<u64>, cx: &mut Context<'_>) -> Poll<bool> {
+        if self.closed {
+            if let Some(ticket) = ticket.take() {
+                self.waiters.retain(|(t, _)| *t != ticket);
+            }
+            return Poll::Ready(false);
+        }
+
+        match *ticket {
+            None if self.waiters.is_empty() && self.has_space() => Poll::Ready(true),
+            None => {
+                let t = self.next_ticket;
+                self.next_ticket += 1;
+                self.waiters.push_back((t, cx.waker().clone()));
+                *ticket = Some(t);
+                Poll::Pending
+            }
+            Some(t) => {
+                if self.has_space() && self.waiters.front().is_some_and(|(f, _)| *f == t) {
+                    self.waiters.pop_front();
+                    *ticket = None;
+                    return Poll::Ready(true);
+                }
+                if let Some((_, waker)) = self.waiters.iter_mut().find(|(f, _)| *f == t) {
+                    if !waker.will_wake(cx.waker()) {
+                        *waker = cx.waker().clone();
+                    }
+                }
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Removes pending sender from the queue.
+    /// Returns waker of the next sender if the removed one was first.
+    fn cancel(&mut self, ticket: u64) -> Option<Waker> {
+        let front = self.waiters.front().is_some_and(|(t, _)| *t == ticket);
+        self.waiters.retain(|(t, _)| *t != ticket);
+        if front {
+            self.front_waker()
+        } else {
+            None
+        }
+    }
+
+    fn push(&mut self, value: T) -> [Option<Waker>; 2] {
+        self.queue.push_back(value);
+        [self.receiver.take(), self.front_waker()]
+    }
+}
+
+fn wake(wakers: impl IntoIterator<Item = Option<Waker>>) {
+    for waker in wakers.into_iter().flatten() {
+        waker.wake();
+    }
+}
+
+/// Creates new channel that holds at most `cap` values.
+///
+/// # Panics
+///
+/// Panics if `cap` is zero.
+pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>)
+where
+    T: MaybeSend,
+{
+    assert!(cap > 0, "Channel capacity must be greater than zero");
+
+    let chan = Rc::new(Mutex::new(State {
+        queue: VecDeque::new(),
+        cap,
+        reserved: 0,
+        senders: 1,
+        closed: false,
+        receiver: None,
+        waiters: VecDeque::new(),
+        next_ticket: 0,
+    }));
+
+    (
+        Sender {
+            chan: chan.clone(),
+            ticket: None,
+            reserved: false,
+        },
+        Receiver { chan },
+    )
+}
+
+/// Sending half of the channel.\
+/// Can be cloned to send values from multiple places.
+pub struct Sender<T> {
+    chan: Rc<Mutex<State<T>>>,
+    // Used by `Sink` implementation.
+    ticket: Option<u64>,
+    reserved: bool,
+}
+
+impl<T> Sender<T> {
+    /// Sends value to the [`Receiver`], waiting while the channel is full.\
+    /// Resolves to [`SendError`] with the value if the channel was closed.
+    ///
+    /// Senders waiting for space are served in FIFO order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use {maybe_sync::future::mpsc, std::{future::Future, pin::pin, task::{Context, Poll, Waker}}};
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let (tx, mut rx) = mpsc::channel(1);
+    /// tx.try_send(0).unwrap();
+    ///
+    /// let mut first = pin!(tx.send(1));
+    /// let mut second = pin!(tx.send(2));
+    /// assert!(first.as_mut().poll(&mut cx).is_pending());
+    /// assert!(second.as_mut().poll(&mut cx).is_pending());
+    ///
+    /// assert_eq!(rx.try_recv(), Ok(0));
+    /// assert!(second.as_mut().poll(&mut cx).is_pending());
+    /// assert_eq!(first.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+    ///
+    /// assert_eq!(rx.try_recv(), Ok(1));
+    /// assert_eq!(second.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+    /// assert_eq!(rx.try_recv(), Ok(2));
+    /// ```
+    ///
+    /// [`Receiver`]: ./struct.Receiver.html
+    /// [`SendError`]: ../../mpsc/struct.SendError.html
+    pub fn send(&self, value: T) -> SendFuture<'_, T> {
+        SendFuture {
+            sender: self,
+            value: Some(value),
+            ticket: None,
+        }
+    }
+
+    /// Attempts to send value to the [`Receiver`] without waiting.
+    ///
+    /// Returns [`TrySendError::Full`] if the channel is full
+    /// or other senders are waiting,
+    /// or [`TrySendError::Disconnected`] if the channel was closed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{future::mpsc, mpsc::TrySendError};
+    /// let (tx, mut rx) = mpsc::channel(1);
+    /// tx.try_send(1).unwrap();
+    /// assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+    ///
+    /// rx.close();
+    /// assert_eq!(tx.try_send(3), Err(TrySendError::Disconnected(3)));
+    /// assert_eq!(rx.try_recv(), Ok(1));
+    /// ```
+    ///
+    /// [`Receiver`]: ./struct.Receiver.html
+    /// [`TrySendError::Full`]: ../../mpsc/enum.TrySendError.html#variant.Full
+    /// [`TrySendError::Disconnected`]: ../../mpsc/enum.TrySendError.html#variant.Disconnected
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.chan.lock();
+        if state.closed {
+            return Err(TrySendError::Disconnected(value));
+        }
+        if !state.waiters.is_empty() || !state.has_space() {
+            return Err(TrySendError::Full(value));
+        }
+        let wakers = state.push(value);
+        drop(state);
+        wake(wakers);
+        Ok(())
+    }
+
+    /// Returns `true` if the channel was closed.
+    pub fn is_closed(&self) -> bool {
+        self.chan.lock().closed
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.chan.lock().senders += 1;
+        Sender {
+            chan: self.chan.clone(),
+            ticket: None,
+            reserved: false,
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.chan.lock();
+        let mut next = None;
+        if let Some(ticket) = self.ticket {
+            next = state.cancel(ticket);
+        }
+        if self.reserved {
+            state.reserved -= 1;
+            next = state.front_waker();
+        }
+        state.senders -= 1;
+
+        // Wake receiver to observe disconnection.
+        let receiver = if state.senders == 0 {
+            state.receiver.take()
+        } else {
+            None
+        };
+        drop(state);
+        wake([receiver, next]);
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+/// Future returned by [`Sender::send`].
+///
+/// [`Sender::send`]: ./struct.Sender.html#method.send
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendFuture<'a, T> {
+    sender: &'a Sender<T>,
+    value: Option<T>,
+    ticket: Option<u64>,
+}
+
+// Value is never pinned.
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), SendError<T>>> {
+        let me = self.get_mut();
+        let mut state = me.sender.chan.lock();
+        let open = match state.poll_turn(&mut me.ticket, cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(open) => open,
+        };
+
+        let value = me.value.take().expect("SendFuture polled after completion");
+        if !open {
+            return Poll::Ready(Err(SendError(value)));
+        }
+        let wakers = state.push(value);
+        drop(state);
+        wake(wakers);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Drop for SendFuture<'_, T> {
+    fn drop(&mut self) {
+        if let Some(ticket) = self.ticket {
+            let next = self.sender.chan.lock().cancel(ticket);
+            wake([next]);
+        }
+    }
+}
+
+impl<T> Debug for SendFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendFuture").finish()
+    }
+}
+
+/// Receiving half of the channel.
+pub struct Receiver<T> {
+    chan: Rc<Mutex<State<T>>>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives value from the channel.\
+    /// Resolves to `None` when the channel is empty and closed
+    /// or all [`Sender`]s were dropped.
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    pub fn recv(&mut self) -> RecvFuture<'_, T> {
+        RecvFuture { receiver: self }
+    }
+
+    /// Polls to receive value from the channel.\
+    /// Same as polling future returned by [`recv`].
+    ///
+    /// [`recv`]: ./struct.Receiver.html#method.recv
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.chan.lock();
+        if let Some(value) = state.queue.pop_front() {
+            let next = state.front_waker();
+            drop(state);
+            wake([next]);
+            return Poll::Ready(Some(value));
+        }
+        if state.closed || state.senders == 0 {
+            return Poll::Ready(None);
+        }
+        state.receiver = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Attempts to receive value without waiting.
+    ///
+    /// Returns [`TryRecvError::Empty`] if there are no values in the channel,
+    /// or [`TryRecvError::Disconnected`] if, in addition,
+    /// the channel was closed or all [`Sender`]s were dropped.
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    /// [`TryRecvError::Empty`]: ../../mpsc/enum.TryRecvError.html#variant.Empty
+    /// [`TryRecvError::Disconnected`]: ../../mpsc/enum.TryRecvError.html#variant.Disconnected
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut state = self.chan.lock();
+        if let Some(value) = state.queue.pop_front() {
+            let next = state.front_waker();
+            drop(state);
+            wake([next]);
+            Ok(value)
+        } else if state.closed || state.senders == 0 {
+            Err(TryRecvError::Disconnected)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+
+    /// Closes the channel.\
+    /// Values already in the channel can still be received,
+    /// while all pending and future sends fail.
+    pub fn close(&mut self) {
+        let mut state = self.chan.lock();
+        state.closed = true;
+        let waiters = core::mem::take(&mut state.waiters);
+        drop(state);
+        wake(waiters.into_iter().map(|(_, waker)| Some(waker)));
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.close();
+
+        // Drop queued values outside of the lock.
+        let queue = core::mem::take(&mut self.chan.lock().queue);
+        drop(queue);
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish()
+    }
+}
+
+/// Future returned by [`Receiver::recv`].
+///
+/// [`Receiver::recv`]: ./struct.Receiver.html#method.recv
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RecvFuture<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for RecvFuture<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.get_mut().receiver.poll_recv(cx)
+    }
+}
+
+impl<T> Debug for RecvFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvFuture").finish()
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> futures_core::Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.get_mut().poll_recv(cx)
+    }
+}
+
+/// Error returned from `Sink` implementation of [`Sender`]
+/// when the channel was closed.
+///
+/// [`Sender`]: ./struct.Sender.html
+#[cfg(feature = "futures")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "futures")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Closed;
+
+#[cfg(feature = "futures")]
+impl fmt::Display for Closed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sending on a closed channel")
+    }
+}
+
+#[cfg(feature = "futures")]
+impl core::error::Error for Closed {}
+
+/// Reserves a slot in `poll_ready` and fills it in `start_send`.
+///
+/// # Example
+///
+/// ```
+/// # use {futures_sink::Sink, maybe_sync::future::mpsc, std::{pin::Pin, task::{Context, Poll, Waker}}};
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let (mut tx, mut rx) = mpsc::channel(1);
+///
+/// assert_eq!(Pin::new(&mut tx).poll_ready(&mut cx), Poll::Ready(Ok(())));
+/// assert_eq!(tx.try_send(2), Err(maybe_sync::mpsc::TrySendError::Full(2)));
+/// Pin::new(&mut tx).start_send(1).unwrap();
+/// assert_eq!(rx.try_recv(), Ok(1));
+/// ```
+#[cfg(feature = "futures")]
+impl<T> futures_sink::Sink<T> for Sender<T> {
+    type Error = Closed;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        let me = self.get_mut();
+        if me.reserved {
+            return Poll::Ready(Ok(()));
+        }
+
+        let mut state = me.chan.lock();
+        match state.poll_turn(&mut me.ticket, cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(false) => Poll::Ready(Err(Closed)),
+            Poll::Ready(true) => {
+                state.reserved += 1;
+                me.reserved = true;
+                let next = state.front_waker();
+                drop(state);
+                wake([next]);
+                Poll::Ready(Ok(()))
+            }
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Closed> {
+        let me = self.get_mut();
+        assert!(
+            me.reserved,
+            "`poll_ready` must be called before `start_send`"
+        );
+
+        let mut state = me.chan.lock();
+        state.reserved -= 1;
+        me.reserved = false;
+        if state.closed {
+            return Err(Closed);
+        }
+        let wakers = state.push(item);
+        drop(state);
+        wake(wakers);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct UnboundedState<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    closed: bool,
+    receiver: Option<Waker>,
+}
+
+/// Creates new unbounded channel.\
+/// Sending never waits, so there is no backpressure.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::future::{mpsc, now_or_never};
+/// let (tx, mut rx) = mpsc::unbounded();
+/// tx.send(1).unwrap();
+/// tx.clone().send(2).unwrap();
+/// assert_eq!(rx.len(), 2);
+///
+/// drop(tx);
+/// let all = async move { (rx.recv().await, rx.recv().await, rx.recv().await) };
+/// assert_eq!(now_or_never(Box::pin(all)), Some((Some(1), Some(2), None)));
+/// ```
+pub fn unbounded<T>() -> (UnboundedSender<T>, UnboundedReceiver<T>)
+where
+    T: MaybeSend,
+{
+    let chan = Rc::new(Mutex::new(UnboundedState {
+        queue: VecDeque::new(),
+        senders: 1,
+        closed: false,
+        receiver: None,
+    }));
+
+    (
+        UnboundedSender { chan: chan.clone() },
+        UnboundedReceiver { chan },
+    )
+}
+
+/// Sending half of the unbounded channel.\
+/// Can be cloned to send values from multiple places.
+pub struct UnboundedSender<T> {
+    chan: Rc<Mutex<UnboundedState<T>>>,
+}
+
+impl<T> UnboundedSender<T> {
+    /// Sends value to the [`UnboundedReceiver`] without waiting.\
+    /// Returns [`SendError`] with the value if the channel was closed.
+    ///
+    /// [`UnboundedReceiver`]: ./struct.UnboundedReceiver.html
+    /// [`SendError`]: ../../mpsc/struct.SendError.html
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut state = self.chan.lock();
+        if state.closed {
+            return Err(SendError(value));
+        }
+        state.queue.push_back(value);
+        let receiver = state.receiver.take();
+        drop(state);
+        wake([receiver]);
+        Ok(())
+    }
+
+    /// Returns number of values in the channel.
+    pub fn len(&self) -> usize {
+        self.chan.lock().queue.len()
+    }
+
+    /// Returns `true` if there are no values in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.chan.lock().queue.is_empty()
+    }
+
+    /// Returns `true` if the channel was closed.
+    pub fn is_closed(&self) -> bool {
+        self.chan.lock().closed
+    }
+}
+
+impl<T> Clone for UnboundedSender<T> {
+    fn clone(&self) -> Self {
+        self.chan.lock().senders += 1;
+        UnboundedSender {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for UnboundedSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.chan.lock();
+        state.senders -= 1;
+
+        // Wake receiver to observe disconnection.
+        let receiver = if state.senders == 0 {
+            state.receiver.take()
+        } else {
+            None
+        };
+        drop(state);
+        wake([receiver]);
+    }
+}
+
+impl<T> Debug for UnboundedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedSender")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+/// Receiving half of the unbounded channel.
+pub struct UnboundedReceiver<T> {
+    chan: Rc<Mutex<UnboundedState<T>>>,
+}
+
+impl<T> UnboundedReceiver<T> {
+    /// Receives value from the channel.\
+    /// Resolves to `None` when the channel is empty and closed
+    /// or all [`UnboundedSender`]s were dropped.
+    ///
+    /// [`UnboundedSender`]: ./struct.UnboundedSender.html
+    pub fn recv(&mut self) -> UnboundedRecvFuture<'_, T> {
+        UnboundedRecvFuture { receiver: self }
+    }
+
+    /// Polls to receive value from the channel.\
+    /// Same as polling future returned by [`recv`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use {maybe_sync::future::mpsc, std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, task::{Context, Poll, Wake, Waker}}};
+    /// struct Flag(AtomicBool);
+    ///
+    /// impl Wake for Flag {
+    ///     fn wake(self: Arc<Self>) {
+    ///         self.0.store(true, Ordering::Relaxed);
+    ///     }
+    /// }
+    ///
+    /// let flag = Arc::new(Flag(AtomicBool::new(false)));
+    /// let waker = Waker::from(flag.clone());
+    /// let mut cx = Context::from_waker(&waker);
+    ///
+    /// let (tx, mut rx) = mpsc::unbounded();
+    /// assert_eq!(rx.poll_recv(&mut cx), Poll::Pending);
+    /// tx.send(1).unwrap();
+    /// assert!(flag.0.load(Ordering::Relaxed));
+    /// assert_eq!(rx.poll_recv(&mut cx), Poll::Ready(Some(1)));
+    /// ```
+    ///
+    /// [`recv`]: ./struct.UnboundedReceiver.html#method.recv
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.chan.lock();
+        if let Some(value) = state.queue.pop_front() {
+            return Poll::Ready(Some(value));
+        }
+        if state.closed || state.senders == 0 {
+            return Poll::Ready(None);
+        }
+        state.receiver = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Attempts to receive value without waiting.
+    ///
+    /// Returns [`TryRecvError::Empty`] if there are no values in the channel,
+    /// or [`TryRecvError::Disconnected`] if, in addition,
+    /// the channel was closed or all [`UnboundedSender`]s were dropped.
+    ///
+    /// [`UnboundedSender`]: ./struct.UnboundedSender.html
+    /// [`TryRecvError::Empty`]: ../../mpsc/enum.TryRecvError.html#variant.Empty
+    /// [`TryRecvError::Disconnected`]: ../../mpsc/enum.TryRecvError.html#variant.Disconnected
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut state = self.chan.lock();
+        match state.queue.pop_front() {
+            Some(value) => Ok(value),
+            None if state.closed || state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Returns number of values in the channel.
+    pub fn len(&self) -> usize {
+        self.chan.lock().queue.len()
+    }
+
+    /// Returns `true` if there are no values in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.chan.lock().queue.is_empty()
+    }
+
+    /// Closes the channel.\
+    /// Values already in the channel can still be received,
+    /// while all further sends fail.
+    pub fn close(&mut self) {
+        self.chan.lock().closed = true;
+    }
+}
+
+impl<T> Drop for UnboundedReceiver<T> {
+    fn drop(&mut self) {
+        // Drop queued values outside of the lock.
+        let queue = {
+            let mut state = self.chan.lock();
+            state.closed = true;
+            core::mem::take(&mut state.queue)
+        };
+        drop(queue);
+    }
+}
+
+impl<T> Debug for UnboundedReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedReceiver")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Future returned by [`UnboundedReceiver::recv`].
+///
+/// [`UnboundedReceiver::recv`]: ./struct.UnboundedReceiver.html#method.recv
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct UnboundedRecvFuture<'a, T> {
+    receiver: &'a mut UnboundedReceiver<T>,
+}
+
+impl<T> Future for UnboundedRecvFuture<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.get_mut().receiver.poll_recv(cx)
+    }
+}
+
+impl<T> Debug for UnboundedRecvFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedRecvFuture").finish()
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> futures_core::Stream for UnboundedReceiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.get_mut().poll_recv(cx)
+    }
+}
@@ -0,0 +1,312 @@
+//! Single-value channel where receivers observe the latest value.
+//!
+//! Follows `tokio::sync::watch` semantics for versions.
+//! Every value sent bumps channel version,
+//! and each [`Receiver`] remembers the version it has seen.
+//!
+//! * Receiver returned from [`channel`] has seen the initial value.
+//! * Receiver created with [`Sender::subscribe`] has seen the current value.
+//! * Cloned receiver has seen the same version as the original one.
+//!
+//! [`Sender`] and [`Receiver`] are sendable when "sync" feature is enabled
+//! and `T: Send + Sync`.
+//!
+//! # Example
+//!
+//! ```
+//! # use {maybe_sync::future::watch, std::{future::Future, pin::pin, task::{Context, Poll, Waker}}};
+//! let mut cx = Context::from_waker(Waker::noop());
+//! let (tx, mut rx) = watch::channel("initial");
+//! assert_eq!(rx.has_changed(), Ok(false));
+//! assert!(pin!(rx.changed()).poll(&mut cx).is_pending());
+//!
+//! tx.send("updated").unwrap();
+//! let mut late = tx.subscribe();
+//! assert_eq!(rx.has_changed(), Ok(true));
+//! assert_eq!(late.has_changed(), Ok(false));
+//!
+//! assert_eq!(pin!(rx.changed()).poll(&mut cx), Poll::Ready(Ok(())));
+//! assert_eq!(*rx.borrow(), "updated");
+//!
+//! drop(tx);
+//! assert_eq!(pin!(late.changed()).poll(&mut cx), Poll::Ready(Err(watch::RecvError)));
+//! assert_eq!(*late.borrow(), "updated");
+//! ```
+//!
+//! [`channel`]: ./fn.channel.html
+//! [`Sender`]: ./struct.Sender.html
+//! [`Sender::subscribe`]: ./struct.Sender.html#method.subscribe
+//! [`Receiver`]: ./struct.Receiver.html
+
+pub use crate::mpsc::{RecvError, SendError};
+
+use {
+    crate::{MaybeSend, MaybeSync, Mutex, Rc, RwLock, RwLockReadGuard},
+    alloc::vec::Vec,
+    core::{
+        fmt::{self, Debug},
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, Waker},
+    },
+};
+
+struct State {
+    version: u64,
+    receivers: usize,
+    closed: bool,
+    wakers: Vec<Waker>,
+}
+
+struct Shared<T> {
+    value: RwLock<T>,
+    state: Mutex<State>,
+}
+
+impl<T> Shared<T> {
+    fn notify(&self, f: impl FnOnce(&mut State)) {
+        let wakers = {
+            let mut state = self.state.lock();
+            f(&mut state);
+            core::mem::take(&mut state.wakers)
+        };
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// Creates new watch channel with initial value.
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>)
+where
+    T: MaybeSend + MaybeSync,
+{
+    let shared = Rc::new(Shared {
+        value: RwLock::new(init),
+        state: Mutex::new(State {
+            version: 0,
+            receivers: 1,
+            closed: false,
+            wakers: Vec::new(),
+        }),
+    });
+
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared, version: 0 },
+    )
+}
+
+/// Sending half of the watch channel.
+pub struct Sender<T> {
+    shared: Rc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Replaces the value and notifies all receivers.\
+    /// Returns value back if there are no receivers.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.shared.state.lock().receivers == 0 {
+            return Err(SendError(value));
+        }
+        *self.shared.value.write() = value;
+        self.shared.notify(|state| state.version += 1);
+        Ok(())
+    }
+
+    /// Modifies the value in place and notifies all receivers.\
+    /// Unlike [`send`] this works even if there are no receivers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::future::watch;
+    /// let (tx, rx) = watch::channel(vec![1]);
+    /// drop(rx);
+    /// tx.send_modify(|v| v.push(2));
+    /// assert_eq!(*tx.borrow(), [1, 2]);
+    /// ```
+    ///
+    /// [`send`]: ./struct.Sender.html#method.send
+    pub fn send_modify(&self, f: impl FnOnce(&mut T)) {
+        f(&mut *self.shared.value.write());
+        self.shared.notify(|state| state.version += 1);
+    }
+
+    /// Returns reference to the current value.\
+    /// Holds read lock while the reference is alive.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.shared.value.read()
+    }
+
+    /// Creates new receiver that has seen the current value.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.shared.state.lock();
+        state.receivers += 1;
+        Receiver {
+            shared: self.shared.clone(),
+            version: state.version,
+        }
+    }
+
+    /// Returns number of receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().receivers
+    }
+
+    /// Returns `true` if all receivers were dropped.
+    pub fn is_closed(&self) -> bool {
+        self.receiver_count() == 0
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        // Wake receivers to observe disconnection.
+        self.shared.notify(|state| state.closed = true);
+    }
+}
+
+impl<T> Debug for Sender<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("value", &*self.borrow())
+            .finish()
+    }
+}
+
+/// Receiving half of the watch channel.\
+/// Can be cloned to observe the value from multiple places.
+///
+/// # Example
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use maybe_sync::future::watch;
+/// maybe_sync::send_ok::<watch::Receiver<u32>>();
+///
+/// let (tx, rx) = watch::channel(1);
+/// tx.send(2).unwrap();
+///
+/// // Clone has seen the same version as the original.
+/// let mut clone = rx.clone();
+/// assert_eq!(clone.has_changed(), Ok(true));
+/// assert_eq!(*clone.borrow_and_update(), 2);
+/// assert_eq!(clone.has_changed(), Ok(false));
+/// assert_eq!(rx.has_changed(), Ok(true));
+/// ```
+pub struct Receiver<T> {
+    shared: Rc<Shared<T>>,
+    version: u64,
+}
+
+impl<T> Receiver<T> {
+    /// Returns reference to the current value
+    /// without marking it as seen.\
+    /// Holds read lock while the reference is alive.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.shared.value.read()
+    }
+
+    /// Returns reference to the current value and marks it as seen.\
+    /// Holds read lock while the reference is alive.
+    pub fn borrow_and_update(&mut self) -> RwLockReadGuard<'_, T> {
+        let value = self.shared.value.read();
+        self.version = self.shared.state.lock().version;
+        value
+    }
+
+    /// Returns `true` if there is a value this receiver has not seen.\
+    /// Returns [`RecvError`] if [`Sender`] was dropped.
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    /// [`RecvError`]: ../../mpsc/struct.RecvError.html
+    pub fn has_changed(&self) -> Result<bool, RecvError> {
+        let state = self.shared.state.lock();
+        if state.closed {
+            return Err(RecvError);
+        }
+        Ok(state.version != self.version)
+    }
+
+    /// Waits for a value this receiver has not seen and marks it as seen.\
+    /// Resolves to [`RecvError`] if [`Sender`] was dropped
+    /// and there is no such value.
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    /// [`RecvError`]: ../../mpsc/struct.RecvError.html
+    pub fn changed(&mut self) -> Changed<'_, T> {
+        Changed { receiver: self }
+    }
+
+    fn poll_changed(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), RecvError>> {
+        let mut state = self.shared.state.lock();
+        if state.version != self.version {
+            self.version = state.version;
+            return Poll::Ready(Ok(()));
+        }
+        if state.closed {
+            return Poll::Ready(Err(RecvError));
+        }
+        if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().receivers += 1;
+        Receiver {
+            shared: self.shared.clone(),
+            version: self.version,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.state.lock().receivers -= 1;
+    }
+}
+
+impl<T> Debug for Receiver<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("value", &*self.borrow())
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+/// Future returned by [`Receiver::changed`].
+///
+/// [`Receiver::changed`]: ./struct.Receiver.html#method.changed
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Changed<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for Changed<'_, T> {
+    type Output = Result<(), RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), RecvError>> {
+        self.get_mut().receiver.poll_changed(cx)
+    }
+}
+
+impl<T> Debug for Changed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Changed").finish()
+    }
+}
@@ -0,0 +1,206 @@
+use {
+    crate::MaybeSend,
+    alloc::boxed::Box,
+    std::io::{BufRead, Read, Seek, Write},
+};
+
+/// Boxed [`Read`] trait object.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::BoxRead, std::{fs::File, io::{Cursor, Read}, path::PathBuf}};
+/// trait Source {
+///     fn open(&self) -> std::io::Result<BoxRead<'_>>;
+/// }
+///
+/// struct Memory(Vec<u8>);
+///
+/// impl Source for Memory {
+///     fn open(&self) -> std::io::Result<BoxRead<'_>> {
+///         Ok(Box::new(Cursor::new(&self.0[..])))
+///     }
+/// }
+///
+/// struct Disk(PathBuf);
+///
+/// impl Source for Disk {
+///     fn open(&self) -> std::io::Result<BoxRead<'_>> {
+///         Ok(Box::new(File::open(&self.0)?))
+///     }
+/// }
+///
+/// let path = std::env::temp_dir().join("maybe-sync-box-read-doctest");
+/// std::fs::write(&path, b"data").unwrap();
+///
+/// let sources: [&dyn Source; 2] = [&Memory(b"data".to_vec()), &Disk(path.clone())];
+/// for source in sources {
+///     let mut data = Vec::new();
+///     source.open().unwrap().read_to_end(&mut data).unwrap();
+///     assert_eq!(data, b"data");
+/// }
+///
+/// std::fs::remove_file(path).unwrap();
+/// ```
+///
+/// [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+pub type BoxRead<'a> = Box<crate::dyn_maybe_send!(Read + 'a)>;
+
+/// Boxed [`Write`] trait object.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+pub type BoxWrite<'a> = Box<crate::dyn_maybe_send!(Write + 'a)>;
+
+/// Boxed [`BufRead`] trait object.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// [`BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+pub type BoxBufRead<'a> = Box<crate::dyn_maybe_send!(BufRead + 'a)>;
+
+/// Combination of [`Read`] and [`Seek`] traits usable as trait object.\
+/// Implemented for all types that implement both.
+///
+/// [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+/// [`Seek`]: https://doc.rust-lang.org/std/io/trait.Seek.html
+pub trait SeekRead: Read + Seek {}
+
+impl<T> SeekRead for T where T: Read + Seek + ?Sized {}
+
+/// Boxed [`SeekRead`] trait object.
+///
+/// Sendable when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::BoxSeekRead, std::io::{Cursor, Read, Seek, SeekFrom}};
+/// let mut reader: BoxSeekRead<'_> = Box::new(Cursor::new(b"data"));
+/// reader.seek(SeekFrom::Start(2)).unwrap();
+/// let mut tail = String::new();
+/// reader.read_to_string(&mut tail).unwrap();
+/// assert_eq!(tail, "ta");
+/// ```
+///
+/// [`SeekRead`]: ./trait.SeekRead.html
+pub type BoxSeekRead<'a> = Box<crate::dyn_maybe_send!(SeekRead + 'a)>;
+
+/// Extension trait for readers to box them into [`BoxRead`].
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{BoxRead, MaybeSendReadExt}, std::io::Read};
+/// let mut reader: BoxRead<'_> = (&b"data"[..]).boxed_read();
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data).unwrap();
+/// assert_eq!(data, b"data");
+/// ```
+///
+/// Values that are both readers and writers can be boxed as either.
+///
+/// ```
+/// # use {maybe_sync::*, std::io::{Cursor, Read, Write}};
+/// let mut writer: BoxWrite<'_> = Cursor::new(Vec::new()).boxed_write();
+/// writer.write_all(b"data").unwrap();
+///
+/// let mut reader: BoxRead<'_> = Cursor::new(b"data".to_vec()).boxed_read();
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data).unwrap();
+/// assert_eq!(data, b"data");
+/// ```
+///
+/// [`BoxRead`]: ./type.BoxRead.html
+pub trait MaybeSendReadExt: Read {
+    /// Boxes the reader into [`BoxRead`].
+    ///
+    /// [`BoxRead`]: ./type.BoxRead.html
+    fn boxed_read<'a>(self) -> BoxRead<'a>
+    where
+        Self: Sized + MaybeSend + 'a,
+    {
+        Box::new(self)
+    }
+}
+
+impl<R> MaybeSendReadExt for R where R: Read + ?Sized {}
+
+/// Extension trait for writers to box them into [`BoxWrite`].
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{BoxWrite, MaybeSendWriteExt}, std::io::Write};
+/// let mut data = Vec::new();
+/// let mut writer: BoxWrite<'_> = (&mut data).boxed_write();
+/// writer.write_all(b"data").unwrap();
+/// drop(writer);
+/// assert_eq!(data, b"data");
+/// ```
+///
+/// [`BoxWrite`]: ./type.BoxWrite.html
+pub trait MaybeSendWriteExt: Write {
+    /// Boxes the writer into [`BoxWrite`].
+    ///
+    /// [`BoxWrite`]: ./type.BoxWrite.html
+    fn boxed_write<'a>(self) -> BoxWrite<'a>
+    where
+        Self: Sized + MaybeSend + 'a,
+    {
+        Box::new(self)
+    }
+}
+
+impl<W> MaybeSendWriteExt for W where W: Write + ?Sized {}
+
+/// Boxes the reader into [`BoxRead`].\
+/// Same as [`MaybeSendReadExt::boxed_read`] usable as function.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{box_read, BoxRead}, std::io::{Cursor, Read}};
+/// let readers: Vec<BoxRead<'_>> = vec![
+///     box_read(Cursor::new(b"head".to_vec())),
+///     box_read(Cursor::new(b"tail")),
+/// ];
+///
+/// let mut data = String::new();
+/// for mut reader in readers {
+///     reader.read_to_string(&mut data).unwrap();
+/// }
+/// assert_eq!(data, "headtail");
+/// ```
+///
+/// [`BoxRead`]: ./type.BoxRead.html
+/// [`MaybeSendReadExt::boxed_read`]: ./trait.MaybeSendReadExt.html#method.boxed_read
+pub fn box_read<'a>(reader: impl Read + MaybeSend + 'a) -> BoxRead<'a> {
+    Box::new(reader)
+}
+
+/// Boxes the writer into [`BoxWrite`].\
+/// Same as [`MaybeSendWriteExt::boxed_write`] usable as function.
+///
+/// # Example
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use {maybe_sync::{box_write, send_ok, BoxWrite}, std::io::{Cursor, Write}};
+/// send_ok::<BoxWrite<'static>>();
+///
+/// let mut buffer = Cursor::new(Vec::new());
+/// let mut writer = box_write(&mut buffer);
+/// writer.write_all(b"data").unwrap();
+/// drop(writer);
+/// assert_eq!(buffer.into_inner(), b"data");
+/// ```
+///
+/// [`BoxWrite`]: ./type.BoxWrite.html
+/// [`MaybeSendWriteExt::boxed_write`]: ./trait.MaybeSendWriteExt.html#method.boxed_write
+pub fn box_write<'a>(writer: impl Write + MaybeSend + 'a) -> BoxWrite<'a> {
+    Box::new(writer)
+}
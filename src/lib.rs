@@ -32,6 +32,8 @@
 //! allowing using multithreaded future executors.
 //! And without "sync" feature [`maybe_sync::BoxFuture`] is
 //! a non-sendable boxed future and only then [`FetchSource`] exists.
+//! [`task::Spawn`] is such a trait, ready for crates to share
+//! one executor handle.
 //!
 //! Similar story with ECS crates.
 //! Most of them require that components are [`Send`] + [`Sync`]
@@ -68,6 +70,27 @@
 //! exist only when "sync" feature is not enabled.
 //! It can be used as function argument type when [`MaybeSend`] bound is placed.
 //!
+//! [`LocalBoxFuture`] is never sendable regardless of "sync" feature.
+//! Use it for code paths that are thread-local even in multithreaded
+//! applications, e.g. main-thread-only windowing.
+//! [`BoxFuture`] can always be converted into [`LocalBoxFuture`]
+//! with [`future::into_local`].
+//!
+//! With "std" feature application can register its executor
+//! with [`set_global_spawner`], e.g. wrapping `tokio::spawn`
+//! or [`wasm_bindgen_futures::spawn_local`],
+//! and libraries can spawn futures with [`spawn`] without knowing which one is used.
+//!
+//! With "macros" feature [`maybe_async_trait`] attribute turns `async fn`
+//! in traits and impls into methods returning [`BoxFuture`].
+//! [`boxed_future`] does the same for free functions and inherent methods.
+//! [`maybe_send_bounds`] attribute adds [`MaybeSend`] bounds
+//! to a trait and its associated types.
+//! [`maybe_bounds!`] appends them to where clauses of any items.
+//! [`define_dyn_alias!`] declares trait object aliases with matching markers.
+//! [`AssertMaybeSend`] and [`AssertMaybeSync`] derives point compile errors
+//! at fields that break sendability.
+//!
 //! # Rc
 //!
 //! Type alias to [`alloc::rc::Rc`] when "sync" feature is not enabled, or
@@ -80,6 +103,67 @@
 //! thin wrapper arond [`core::cell::RefCell`] otherwise. Serves for optimization
 //! purposes for crates that already use [`maybe-sync`] crate.
 //!
+//! Unlike `std::sync::Mutex` it is never poisoned and `lock` returns guard directly.
+//! [`MutexExt`] provides `lock_checked` returning `Result` to ease porting
+//! from `std::sync::Mutex`.
+//!
+//! With "serde" feature `Mutex` and `RwLock` are serialized transparently,
+//! exactly as the value they hold, whether "sync" feature is enabled or not.
+//!
+//! # RwLock
+//!
+//! Type alias to [`parking_lot::RwLock`] when "sync" feature is enabled, or
+//! thin wrapper arond [`core::cell::RefCell`] otherwise.
+//!
+//! # Atomics
+//!
+//! Type aliases to atomic types from [`core::sync::atomic`] when "sync" feature
+//! is enabled, or [`core::cell::Cell`] with corresponding value type otherwise.
+//!
+//! [`AtomicI64`] and [`AtomicU64`] are available on targets with 64-bit atomics
+//! or with "portable-atomic" feature.
+//!
+//! Targets without native atomic operations (like `thumbv6m-none-eabi`) can enable
+//! "portable-atomic" feature. Then atomic types from [`portable-atomic`] crate are
+//! used instead of [`core::sync::atomic`] when "sync" feature is enabled.
+//!
+//! "portable-atomic" feature also enables [`AtomicI128`] and [`AtomicU128`]
+//! aliases, as `core` has no 128-bit atomics.
+//! They are backed by [`portable-atomic`] crate when "sync" feature is enabled,
+//! and by [`core::cell::Cell`] otherwise, like all other atomics.
+//! On targets without native 128-bit atomic operations (like `x86_64` without `cmpxchg16b`)
+//! [`portable-atomic`] detects support at runtime where possible
+//! and falls back to lock-based implementation otherwise.
+//!
+//! # Strict mode
+//!
+//! Without "sync" feature [`MaybeSend`] and [`MaybeSync`] are implemented for all types.
+//! Generic code that accidentally relies on that compiles fine
+//! until some downstream crate enables "sync" feature.
+//!
+//! Enabling "strict" feature without "sync" makes [`MaybeSend`] and [`MaybeSync`]
+//! reexports of [`Send`] and [`Sync`] while all other types stay singlethreaded.
+//! This feature is intended for CI only, to check that bounds are placed correctly
+//! without building multithreaded version.
+//! This feature has no effect when "sync" feature is enabled.
+//!
+//! Note that `Rc`, `Mutex` and other singlethreaded types from this crate
+//! do not satisfy those bounds in "strict" mode.
+//! Code that requires them to be [`MaybeSend`] or [`MaybeSync`] should be checked
+//! with "sync" feature instead.
+//!
+//! A typical CI workflow of a library that forwards "sync" feature
+//! to this crate looks like this:
+//!
+//! ```text
+//! cargo test
+//! cargo test --features sync
+//! cargo check --features maybe-sync/strict
+//! ```
+//!
+//! Binary crates can check that "sync" feature was unified as expected
+//! with [`assert_feature_matches!`].
+//!
 //! [`Send`]: https://doc.rust-lang.org/std/marker/trait.Send.html
 //! [`Sync`]: https://doc.rust-lang.org/std/marker/trait.Sync.html
 //! [`web-sys`]: https://docs.rs/web-sys
@@ -89,15 +173,38 @@
 //! [`actix-rt`]: https://docs.rs/actix-rt
 //! [`FetchSource`]: https://docs.rs/goods/0.5/wasm32-unknown-unknown/goods/struct.FetchSource.html
 //! [`wasm_bindgen_futures::spawn_local`]: https://docs.rs/wasm-bindgen-futures/0.4/wasm_bindgen_futures/fn.spawn_local.html
+//! [`task::Spawn`]: ./task/trait.Spawn.html
 //! [`goods::Spawn::spawn`]: https://docs.rs/goods/0.5/goods/trait.Spawn.html#tymethod.spawn
 //! [`maybe_sync::BoxFuture`]: ./type.BoxFuture.html
+//! [`BoxFuture`]: ./type.BoxFuture.html
+//! [`LocalBoxFuture`]: ./type.LocalBoxFuture.html
+//! [`future::into_local`]: ./future/fn.into_local.html
+//! [`set_global_spawner`]: ./fn.set_global_spawner.html
+//! [`spawn`]: ./fn.spawn.html
+//! [`maybe_async_trait`]: ./attr.maybe_async_trait.html
+//! [`boxed_future`]: ./attr.boxed_future.html
+//! [`maybe_send_bounds`]: ./attr.maybe_send_bounds.html
+//! [`maybe_bounds!`]: ./macro.maybe_bounds.html
+//! [`define_dyn_alias!`]: ./macro.define_dyn_alias.html
+//! [`AssertMaybeSend`]: ./derive.AssertMaybeSend.html
+//! [`AssertMaybeSync`]: ./derive.AssertMaybeSync.html
+//! [`assert_feature_matches!`]: ./macro.assert_feature_matches.html
 //! [`MaybeSend`]: ./trait.MaybeSend.html
 //! [`MaybeSync`]: ./trait.MaybeSync.html
 //! [`alloc::rc::Rc`]: https://doc.rust-lang.org/alloc/rc/struct.Rc.html
 //! [`alloc::sync::Arc`]: https://doc.rust-lang.org/alloc/sync/struct.Arc.html
 //! [`maybe-sync`]: ./index.html
 //! [`parking_lot::Mutex`]: https://docs.rs/parking_lot/0.10/parking_lot/type.Mutex.html
+//! [`MutexExt`]: ./trait.MutexExt.html
 //! [`core::cell::RefCell`]: https://doc.rust-lang.org/core/cell/struct.RefCell.html
+//! [`parking_lot::RwLock`]: https://docs.rs/parking_lot/0.10/parking_lot/type.RwLock.html
+//! [`core::sync::atomic`]: https://doc.rust-lang.org/core/sync/atomic/index.html
+//! [`core::cell::Cell`]: https://doc.rust-lang.org/core/cell/struct.Cell.html
+//! [`portable-atomic`]: https://docs.rs/portable-atomic
+//! [`AtomicI128`]: ./type.AtomicI128.html
+//! [`AtomicI64`]: ./type.AtomicI64.html
+//! [`AtomicU64`]: ./type.AtomicU64.html
+//! [`AtomicU128`]: ./type.AtomicU128.html
 
 #![no_std]
 #![cfg_attr(all(doc, feature = "unstable-doc"), feature(doc_cfg))]
@@ -105,11 +212,50 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub mod rc;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub mod future;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub mod mpsc;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub mod oneshot;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub mod time;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub mod task;
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+}
+
 #[cfg(feature = "sync")]
 mod sync {
     #[cfg(feature = "alloc")]
     use core::{future::Future, pin::Pin};
 
+    #[cfg(not(feature = "portable-atomic"))]
+    use core::sync::atomic;
+
+    #[cfg(feature = "portable-atomic")]
+    use portable_atomic as atomic;
+
     /// Reexports of the actual marker traits from core.
     pub use core::marker::{Send as MaybeSend, Sync as MaybeSync};
 
@@ -130,14 +276,16 @@ mod sync {
     /// A pointer type which can be shared, but only within single thread
     /// where it was created when "sync" feature is not enabled.
     ///
+    /// `Rc<T>` satisfies `MaybeSend` and `MaybeSync` bounds only when
+    /// `T: MaybeSend + MaybeSync`. Use [`share_ok`] to check payload type early.
+    ///
     /// # Example
     ///
     /// ```
     /// # use {maybe_sync::{MaybeSend, Rc}, std::fmt::Debug};
     ///
     /// fn maybe_sends<T: MaybeSend + Debug + 'static>(val: T) {
-    ///   #[cfg(feature = "sync")]
-    ///   {
+    ///   maybe_sync::if_sync! {
     ///     // If this code is compiled then `MaybeSend` is alias to `std::marker::Send`.
     ///     std::thread::spawn(move || { println!("{:?}", val) });
     ///   }
@@ -146,10 +294,35 @@ mod sync {
     /// // Unlike `std::rc::Rc` this `maybe_sync::Rc` always satisfies `MaybeSend` bound.
     /// maybe_sends(Rc::new(42));
     /// ```
+    ///
+    /// [`share_ok`]: ./fn.share_ok.html
     #[cfg(feature = "alloc")]
     #[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
     pub type Rc<T> = alloc::sync::Arc<T>;
 
+    /// Non-owning version of [`Rc`] that can be upgraded
+    /// while the value is still alive.
+    ///
+    /// A type alias to `std::sync::Weak` when "sync" feature is enabled.\
+    /// A type alias to `std::rc::Weak` when "sync" feature is not enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Rc, Weak};
+    /// let rc = Rc::new(42);
+    /// let weak: Weak<u32> = Rc::downgrade(&rc);
+    /// assert_eq!(weak.upgrade().as_deref(), Some(&42));
+    ///
+    /// drop(rc);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    ///
+    /// [`Rc`]: ./type.Rc.html
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+    pub type Weak<T> = alloc::sync::Weak<T>;
+
     /// Mutex implementation to use in conjunction with `MaybeSync` bound.
     ///
     /// A type alias to `parking_lot::Mutex` when "sync" feature is enabled.\
@@ -161,8 +334,7 @@ mod sync {
     /// # use {maybe_sync::{MaybeSend, Mutex}, std::{fmt::Debug, sync::Arc}};
     ///
     /// fn maybe_sends<T: MaybeSend + Debug + 'static>(val: Arc<Mutex<T>>) {
-    ///   #[cfg(feature = "sync")]
-    ///   {
+    ///   maybe_sync::if_sync! {
     ///     // If this code is compiled then `MaybeSend` is alias to `std::marker::Send`,
     ///     // and `Mutex` is `parking_lot::Mutex`.
     ///     std::thread::spawn(move || { println!("{:?}", *val.lock()) });
@@ -174,15 +346,98 @@ mod sync {
     /// // even if feature "sync" is enabeld.
     /// maybe_sends(Arc::new(Mutex::new(42)));
     /// ```
+    ///
+    /// With "serde" feature `Mutex<T>` is serialized exactly as `T`
+    /// whether "sync" feature is enabled or not.
+    ///
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use {maybe_sync::Mutex, serde::{Deserialize, Serialize}};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Inner {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Config {
+    ///     #[serde(flatten)]
+    ///     inner: Mutex<Inner>,
+    ///     port: u16,
+    /// }
+    ///
+    /// // Same bytes are produced and accepted by both singlethreaded
+    /// // and multithreaded builds.
+    /// let json = r#"{"name":"server","port":8080}"#;
+    /// let config: Config = serde_json::from_str(json).unwrap();
+    /// assert_eq!(config.inner.lock().name, "server");
+    /// assert_eq!(serde_json::to_string(&config).unwrap(), json);
+    /// # }
+    /// ```
     pub type Mutex<T> = parking_lot::Mutex<T>;
 
+    /// RAII guard returned by `Mutex::lock`.
+    /// The lock is released when the guard is dropped.
+    ///
+    /// A type alias to `parking_lot::MutexGuard` when "sync" feature is enabled.\
+    /// A type alias to `std::cell::RefMut` when "sync" feature is not enabled.
+    pub type MutexGuard<'a, T> = parking_lot::MutexGuard<'a, T>;
+
+    /// RAII guard returned by [`MutexGuardMapExt::try_map`].
+    /// The lock is released when the guard is dropped.
+    ///
+    /// A type alias to `parking_lot::MappedMutexGuard` when "sync" feature is enabled.\
+    /// A type alias to `std::cell::RefMut` when "sync" feature is not enabled.
+    ///
+    /// [`MutexGuardMapExt::try_map`]: ./trait.MutexGuardMapExt.html#tymethod.try_map
+    pub type MappedMutexGuard<'a, T> = parking_lot::MappedMutexGuard<'a, T>;
+
+    /// Reader-writer lock implementation to use in conjunction with `MaybeSync` bound.
+    ///
+    /// A type alias to `parking_lot::RwLock` when "sync" feature is enabled.\
+    /// A wrapper type around `std::cell::RefCell` when "sync" feature is not enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use {maybe_sync::{MaybeSend, MaybeSync, RwLock}, std::{fmt::Debug, sync::Arc}};
+    ///
+    /// fn maybe_shares<T: MaybeSend + MaybeSync + Debug + 'static>(val: Arc<RwLock<T>>) {
+    ///   maybe_sync::if_sync! {
+    ///     // If this code is compiled then `RwLock` is `parking_lot::RwLock`.
+    ///     std::thread::spawn(move || { println!("{:?}", *val.read()) });
+    ///   }
+    /// }
+    ///
+    /// let lock = Arc::new(RwLock::new(42));
+    /// *lock.write() += 1;
+    /// assert_eq!(*lock.read(), 43);
+    /// maybe_shares(lock);
+    /// ```
+    pub type RwLock<T> = parking_lot::RwLock<T>;
+
+    /// RAII guard returned by `RwLock::read`.
+    /// The shared lock is released when the guard is dropped.
+    ///
+    /// A type alias to `parking_lot::RwLockReadGuard` when "sync" feature is enabled.\
+    /// A type alias to `std::cell::Ref` when "sync" feature is not enabled.
+    pub type RwLockReadGuard<'a, T> = parking_lot::RwLockReadGuard<'a, T>;
+
+    /// RAII guard returned by `RwLock::write`.
+    /// The exclusive lock is released when the guard is dropped.
+    ///
+    /// A type alias to `parking_lot::RwLockWriteGuard` when "sync" feature is enabled.\
+    /// A type alias to `std::cell::RefMut` when "sync" feature is not enabled.
+    pub type RwLockWriteGuard<'a, T> = parking_lot::RwLockWriteGuard<'a, T>;
+
     /// A boolean type which can be safely shared between threads
     /// when "sync" feature is enabled.\
     /// A boolean type with non-threadsafe interior mutability
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a bool.
-    pub type AtomicBool = core::sync::atomic::AtomicBool;
+    pub type AtomicBool = atomic::AtomicBool;
 
     /// A integer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -190,7 +445,7 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a i8.
-    pub type AtomicI8 = core::sync::atomic::AtomicI8;
+    pub type AtomicI8 = atomic::AtomicI8;
 
     /// A integer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -198,7 +453,7 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a i16.
-    pub type AtomicI16 = core::sync::atomic::AtomicI16;
+    pub type AtomicI16 = atomic::AtomicI16;
 
     /// A integer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -206,7 +461,7 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a i32.
-    pub type AtomicI32 = core::sync::atomic::AtomicI32;
+    pub type AtomicI32 = atomic::AtomicI32;
 
     /// A integer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -214,7 +469,7 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a isize.
-    pub type AtomicIsize = core::sync::atomic::AtomicIsize;
+    pub type AtomicIsize = atomic::AtomicIsize;
 
     /// A integer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -222,7 +477,7 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a i8.
-    pub type AtomicU8 = core::sync::atomic::AtomicU8;
+    pub type AtomicU8 = atomic::AtomicU8;
 
     /// A integer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -230,7 +485,7 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a i16.
-    pub type AtomicU16 = core::sync::atomic::AtomicU16;
+    pub type AtomicU16 = atomic::AtomicU16;
 
     /// A integer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -238,7 +493,7 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a i32.
-    pub type AtomicU32 = core::sync::atomic::AtomicU32;
+    pub type AtomicU32 = atomic::AtomicU32;
 
     /// A integer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -246,7 +501,25 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a isize.
-    pub type AtomicUsize = core::sync::atomic::AtomicUsize;
+    pub type AtomicUsize = atomic::AtomicUsize;
+
+    /// A 64-bit signed integer type which can be safely shared between threads
+    /// when "sync" feature is enabled.\
+    /// A 64-bit signed integer type with non-threadsafe interior mutability
+    /// when "sync" feature is not enabled.
+    ///
+    /// Available on targets with 64-bit atomics or with "portable-atomic" feature.
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+    pub type AtomicI64 = atomic::AtomicI64;
+
+    /// A 64-bit unsigned integer type which can be safely shared between threads
+    /// when "sync" feature is enabled.\
+    /// A 64-bit unsigned integer type with non-threadsafe interior mutability
+    /// when "sync" feature is not enabled.
+    ///
+    /// Available on targets with 64-bit atomics or with "portable-atomic" feature.
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+    pub type AtomicU64 = atomic::AtomicU64;
 
     /// A raw pointer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
@@ -254,12 +527,41 @@ mod sync {
     /// when "sync" feature is not enabled.
     ///
     /// This type has the same in-memory representation as a isize.
-    pub type AtomicPtr<T> = core::sync::atomic::AtomicPtr<T>;
+    pub type AtomicPtr<T> = atomic::AtomicPtr<T>;
+
+    /// A 128-bit signed integer type which can be safely shared between threads
+    /// when "sync" feature is enabled.\
+    /// A 128-bit signed integer type with non-threadsafe interior mutability
+    /// when "sync" feature is not enabled.
+    ///
+    /// Requires "portable-atomic" feature as there is no 128-bit atomics in `core`.
+    #[cfg(feature = "portable-atomic")]
+    #[cfg_attr(
+        all(doc, feature = "unstable-doc"),
+        doc(cfg(feature = "portable-atomic"))
+    )]
+    pub type AtomicI128 = portable_atomic::AtomicI128;
+
+    /// A 128-bit unsigned integer type which can be safely shared between threads
+    /// when "sync" feature is enabled.\
+    /// A 128-bit unsigned integer type with non-threadsafe interior mutability
+    /// when "sync" feature is not enabled.
+    ///
+    /// Requires "portable-atomic" feature as there is no 128-bit atomics in `core`.
+    #[cfg(feature = "portable-atomic")]
+    #[cfg_attr(
+        all(doc, feature = "unstable-doc"),
+        doc(cfg(feature = "portable-atomic"))
+    )]
+    pub type AtomicU128 = portable_atomic::AtomicU128;
 }
 
 #[cfg(not(feature = "sync"))]
 mod unsync {
-    use core::cell::{RefCell, RefMut};
+    use core::{
+        cell::{Ref, RefCell, RefMut},
+        fmt::{self, Debug, Formatter},
+    };
 
     #[cfg(feature = "alloc")]
     use core::{future::Future, pin::Pin};
@@ -278,22 +580,22 @@ mod unsync {
     /// # use {maybe_sync::MaybeSend, std::{fmt::Debug, rc::Rc}};
     ///
     /// fn maybe_sends<T: MaybeSend + Debug + 'static>(val: T) {
-    ///   #[cfg(feature = "sync")]
-    ///   {
+    ///   maybe_sync::if_sync! {
     ///     // If this code is compiled then `MaybeSend` is alias to `std::marker::Send`.
     ///     std::thread::spawn(move || { println!("{:?}", val) });
     ///   }
     /// }
     ///
-    /// #[cfg(not(feature = "sync"))]
-    /// {
+    /// maybe_sync::if_unsync! {
     ///   // If this code is compiled then `MaybeSend` dummy markerd implemented for all types.
     ///   maybe_sends(Rc::new(42));
     /// }
     /// ```
+    #[cfg(not(feature = "strict"))]
     pub trait MaybeSend {}
 
     /// All values are maybe sendable.
+    #[cfg(not(feature = "strict"))]
     impl<T> MaybeSend for T where T: ?Sized {}
 
     /// Marker trait that can represent nothing if feature "sync" is not enabled.
@@ -311,24 +613,27 @@ mod unsync {
     /// # use {maybe_sync::MaybeSync, std::{sync::Arc, fmt::Debug, cell::Cell}};
     ///
     /// fn maybe_shares<T: MaybeSync + Debug + 'static>(val: Arc<T>) {
-    ///   #[cfg(feature = "sync")]
-    ///   {
+    ///   maybe_sync::if_sync! {
     ///     // If this code is compiled then `MaybeSync` is alias to `std::marker::Sync`.
     ///     std::thread::spawn(move || { println!("{:?}", val) });
     ///   }
     /// }
     ///
-    /// #[cfg(not(feature = "sync"))]
-    /// {
+    /// maybe_sync::if_unsync! {
     ///   // If this code is compiled then `MaybeSync` dummy markerd implemented for all types.
     ///   maybe_shares(Arc::new(Cell::new(42)));
     /// }
     /// ```
+    #[cfg(not(feature = "strict"))]
     pub trait MaybeSync {}
 
     /// All values are maybe sync.
+    #[cfg(not(feature = "strict"))]
     impl<T> MaybeSync for T where T: ?Sized {}
 
+    #[cfg(feature = "strict")]
+    pub use core::marker::{Send as MaybeSend, Sync as MaybeSync};
+
     /// An owned dynamically typed [`Future`] for use at return position in cases
     /// when type is opaque and existential type cannot be used,
     /// or when multiple types can be returned.
@@ -346,14 +651,17 @@ mod unsync {
     /// A pointer type which can be shared, but only within single thread
     /// where it was created when "sync" feature is not enabled.
     ///
+    /// `Rc<T>` satisfies `MaybeSend` and `MaybeSync` bounds only when
+    /// `T: MaybeSend + MaybeSync`. Use [`share_ok`] to check payload type early.
+    ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+    #[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
     /// # use {maybe_sync::{MaybeSend, Rc}, std::fmt::Debug};
     ///
     /// fn maybe_sends<T: MaybeSend + Debug + 'static>(val: T) {
-    ///   #[cfg(feature = "sync")]
-    ///   {
+    ///   maybe_sync::if_sync! {
     ///     // If this code is compiled then `MaybeSend` is alias to `std::marker::Send`.
     ///     std::thread::spawn(move || { println!("{:?}", val) });
     ///   }
@@ -364,10 +672,35 @@ mod unsync {
     /// // even if feature "sync" is enabeld.
     /// maybe_sends(Rc::new(42));
     /// ```
+    ///
+    /// [`share_ok`]: ./fn.share_ok.html
     #[cfg(feature = "alloc")]
     #[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
     pub type Rc<T> = alloc::rc::Rc<T>;
 
+    /// Non-owning version of [`Rc`] that can be upgraded
+    /// while the value is still alive.
+    ///
+    /// A type alias to `std::sync::Weak` when "sync" feature is enabled.\
+    /// A type alias to `std::rc::Weak` when "sync" feature is not enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Rc, Weak};
+    /// let rc = Rc::new(42);
+    /// let weak: Weak<u32> = Rc::downgrade(&rc);
+    /// assert_eq!(weak.upgrade().as_deref(), Some(&42));
+    ///
+    /// drop(rc);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    ///
+    /// [`Rc`]: ./type.Rc.html
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+    pub type Weak<T> = alloc::rc::Weak<T>;
+
     /// Mutex implementation to use in conjunction with `MaybeSync` bound.
     ///
     /// A type alias to `parking_lot::Mutex` when "sync" feature is enabled.\
@@ -379,8 +712,7 @@ mod unsync {
     /// # use {maybe_sync::{MaybeSend, Mutex}, std::{fmt::Debug, sync::Arc}};
     ///
     /// fn maybe_sends<T: MaybeSend + Debug + 'static>(val: Arc<Mutex<T>>) {
-    ///   #[cfg(feature = "sync")]
-    ///   {
+    ///   maybe_sync::if_sync! {
     ///     // If this code is compiled then `MaybeSend` is alias to `std::marker::Send`,
     ///     // and `Mutex` is `parking_lot::Mutex`.
     ///     std::thread::spawn(move || { println!("{:?}", *val.lock()) });
@@ -392,8 +724,37 @@ mod unsync {
     /// // even if feature "sync" is enabeld.
     /// maybe_sends(Arc::new(Mutex::new(42)));
     /// ```
+    ///
+    /// With "serde" feature `Mutex<T>` is serialized exactly as `T`
+    /// whether "sync" feature is enabled or not.
+    ///
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use {maybe_sync::Mutex, serde::{Deserialize, Serialize}};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Inner {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Config {
+    ///     #[serde(flatten)]
+    ///     inner: Mutex<Inner>,
+    ///     port: u16,
+    /// }
+    ///
+    /// // Same bytes are produced and accepted by both singlethreaded
+    /// // and multithreaded builds.
+    /// let json = r#"{"name":"server","port":8080}"#;
+    /// let config: Config = serde_json::from_str(json).unwrap();
+    /// assert_eq!(config.inner.lock().name, "server");
+    /// assert_eq!(serde_json::to_string(&config).unwrap(), json);
+    /// # }
+    /// ```
     #[repr(transparent)]
-    #[derive(Debug, Default)]
+    #[derive(Default)]
     pub struct Mutex<T: ?Sized> {
         cell: RefCell<T>,
     }
@@ -405,6 +766,11 @@ mod unsync {
                 cell: RefCell::new(value),
             }
         }
+
+        /// Consumes this mutex, returning the underlying data.
+        pub fn into_inner(self) -> T {
+            self.cell.into_inner()
+        }
     }
 
     impl<T> Mutex<T>
@@ -417,7 +783,32 @@ mod unsync {
         /// An RAII guard is returned to allow scoped unlock of the lock.\
         /// When the guard goes out of scope, the mutex will be unlocked.\
         /// Attempts to lock a mutex in the thread which already holds the lock will result in a deadlock.
-        pub fn lock(&self) -> RefMut<T> {
+        ///
+        /// When "sync" feature is not enabled attempt to lock a mutex
+        /// which is already locked panics instead.
+        /// Panic location points to the caller.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// # use {maybe_sync::Mutex, std::{panic, sync::{Arc, Mutex as StdMutex}}};
+        /// let location = Arc::new(StdMutex::new(None));
+        /// let hook_location = location.clone();
+        /// panic::set_hook(Box::new(move |info| {
+        ///     *hook_location.lock().unwrap() = info.location().map(|l| l.line());
+        /// }));
+        ///
+        /// let mutex = Mutex::new(42);
+        /// let _guard = mutex.lock();
+        /// let line = line!() + 1;
+        /// let result = panic::catch_unwind(panic::AssertUnwindSafe(|| { mutex.lock(); }));
+        /// let _ = panic::take_hook();
+        ///
+        /// assert!(result.is_err());
+        /// assert_eq!(*location.lock().unwrap(), Some(line));
+        /// ```
+        #[track_caller]
+        pub fn lock(&self) -> MutexGuard<'_, T> {
             self.cell.borrow_mut()
         }
 
@@ -426,7 +817,7 @@ mod unsync {
         /// Otherwise, an RAII guard is returned.\
         /// The lock will be unlocked when the guard is dropped.\
         /// This function does not block.
-        pub fn try_lock(&self) -> Option<RefMut<T>> {
+        pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
             self.cell.try_borrow_mut().ok()
         }
 
@@ -439,6 +830,244 @@ mod unsync {
         }
     }
 
+    /// Prints `<locked>` instead of the value if the mutex is locked,
+    /// the same way `parking_lot::Mutex` does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::Mutex;
+    /// let mutex = Mutex::new(42);
+    /// assert_eq!(format!("{:?}", mutex), "Mutex { data: 42 }");
+    ///
+    /// let guard = mutex.lock();
+    /// assert_eq!(format!("{:?}", mutex), "Mutex { data: <locked> }");
+    /// drop(guard);
+    /// ```
+    impl<T> Debug for Mutex<T>
+    where
+        T: Debug + ?Sized,
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            match self.cell.try_borrow() {
+                Ok(data) => f.debug_struct("Mutex").field("data", &&*data).finish(),
+                Err(_) => f
+                    .debug_struct("Mutex")
+                    .field("data", &LockedPlaceholder)
+                    .finish(),
+            }
+        }
+    }
+
+    /// RAII guard returned by `Mutex::lock`.
+    /// The lock is released when the guard is dropped.
+    ///
+    /// A type alias to `parking_lot::MutexGuard` when "sync" feature is enabled.\
+    /// A type alias to `std::cell::RefMut` when "sync" feature is not enabled.
+    pub type MutexGuard<'a, T> = RefMut<'a, T>;
+
+    /// RAII guard returned by [`MutexGuardMapExt::try_map`].
+    /// The lock is released when the guard is dropped.
+    ///
+    /// A type alias to `parking_lot::MappedMutexGuard` when "sync" feature is enabled.\
+    /// A type alias to `std::cell::RefMut` when "sync" feature is not enabled.
+    ///
+    /// [`MutexGuardMapExt::try_map`]: ./trait.MutexGuardMapExt.html#tymethod.try_map
+    pub type MappedMutexGuard<'a, T> = RefMut<'a, T>;
+
+    /// Reader-writer lock implementation to use in conjunction with `MaybeSync` bound.
+    ///
+    /// A type alias to `parking_lot::RwLock` when "sync" feature is enabled.\
+    /// A wrapper type around `std::cell::RefCell` when "sync" feature is not enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use {maybe_sync::{MaybeSend, MaybeSync, RwLock}, std::{fmt::Debug, sync::Arc}};
+    ///
+    /// fn maybe_shares<T: MaybeSend + MaybeSync + Debug + 'static>(val: Arc<RwLock<T>>) {
+    ///   maybe_sync::if_sync! {
+    ///     // If this code is compiled then `RwLock` is `parking_lot::RwLock`.
+    ///     std::thread::spawn(move || { println!("{:?}", *val.read()) });
+    ///   }
+    /// }
+    ///
+    /// let lock = Arc::new(RwLock::new(42));
+    /// *lock.write() += 1;
+    /// assert_eq!(*lock.read(), 43);
+    /// maybe_shares(lock);
+    /// ```
+    #[repr(transparent)]
+    #[derive(Default)]
+    pub struct RwLock<T: ?Sized> {
+        cell: RefCell<T>,
+    }
+
+    impl<T> RwLock<T> {
+        /// Creates a new instance of an `RwLock<T>` which is unlocked.
+        pub fn new(value: T) -> Self {
+            RwLock {
+                cell: RefCell::new(value),
+            }
+        }
+    }
+
+    impl<T> RwLock<T>
+    where
+        T: ?Sized,
+    {
+        /// Locks this `RwLock` with shared read access,
+        /// blocking the current thread until it can be acquired.\
+        /// Returns an RAII guard which will release this thread's shared access
+        /// once it is dropped.
+        ///
+        /// When "sync" feature is not enabled attempt to read-lock `RwLock`
+        /// which is already write-locked panics instead.
+        #[track_caller]
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.cell.borrow()
+        }
+
+        /// Attempts to acquire this `RwLock` with shared read access.\
+        /// If the access could not be granted at this time, then `None` is returned.\
+        /// This function does not block.
+        pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+            self.cell.try_borrow().ok()
+        }
+
+        /// Locks this `RwLock` with exclusive write access,
+        /// blocking the current thread until it can be acquired.\
+        /// Returns an RAII guard which will drop the write access
+        /// of this `RwLock` when dropped.
+        ///
+        /// When "sync" feature is not enabled attempt to write-lock `RwLock`
+        /// which is already locked panics instead.
+        #[track_caller]
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.cell.borrow_mut()
+        }
+
+        /// Attempts to lock this `RwLock` with exclusive write access.\
+        /// If the lock could not be acquired at this time, then `None` is returned.\
+        /// This function does not block.
+        pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+            self.cell.try_borrow_mut().ok()
+        }
+
+        /// Returns a mutable reference to the underlying data.\
+        /// Since this call borrows the `RwLock` mutably,
+        /// no actual locking needs to take place -
+        /// the mutable borrow statically guarantees no locks exist.
+        pub fn get_mut(&mut self) -> &mut T {
+            self.cell.get_mut()
+        }
+    }
+
+    /// Prints `<locked>` instead of the value if the lock is write-locked,
+    /// the same way `parking_lot::RwLock` does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::RwLock;
+    /// let lock = RwLock::new(42);
+    /// let guard = lock.read();
+    /// assert_eq!(format!("{:?}", lock), "RwLock { data: 42 }");
+    /// drop(guard);
+    ///
+    /// let guard = lock.write();
+    /// assert_eq!(format!("{:?}", lock), "RwLock { data: <locked> }");
+    /// drop(guard);
+    /// ```
+    impl<T> Debug for RwLock<T>
+    where
+        T: Debug + ?Sized,
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            match self.cell.try_borrow() {
+                Ok(data) => f.debug_struct("RwLock").field("data", &&*data).finish(),
+                Err(_) => f
+                    .debug_struct("RwLock")
+                    .field("data", &LockedPlaceholder)
+                    .finish(),
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<T> serde::Serialize for Mutex<T>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            self.lock().serialize(serializer)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, T> serde::Deserialize<'de> for Mutex<T>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            T::deserialize(deserializer).map(Mutex::new)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<T> serde::Serialize for RwLock<T>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            self.read().serialize(serializer)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, T> serde::Deserialize<'de> for RwLock<T>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            T::deserialize(deserializer).map(RwLock::new)
+        }
+    }
+
+    struct LockedPlaceholder;
+
+    impl Debug for LockedPlaceholder {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str("<locked>")
+        }
+    }
+
+    /// RAII guard returned by `RwLock::read`.
+    /// The shared lock is released when the guard is dropped.
+    ///
+    /// A type alias to `parking_lot::RwLockReadGuard` when "sync" feature is enabled.\
+    /// A type alias to `std::cell::Ref` when "sync" feature is not enabled.
+    pub type RwLockReadGuard<'a, T> = Ref<'a, T>;
+
+    /// RAII guard returned by `RwLock::write`.
+    /// The exclusive lock is released when the guard is dropped.
+    ///
+    /// A type alias to `parking_lot::RwLockWriteGuard` when "sync" feature is enabled.\
+    /// A type alias to `std::cell::RefMut` when "sync" feature is not enabled.
+    pub type RwLockWriteGuard<'a, T> = RefMut<'a, T>;
+
     /// A boolean type which can be safely shared between threads
     /// when "sync" feature is enabled.\
     /// A boolean type with non-threadsafe interior mutability
@@ -511,6 +1140,24 @@ mod unsync {
     /// This type has the same in-memory representation as a isize.
     pub type AtomicUsize = core::cell::Cell<usize>;
 
+    /// A 64-bit signed integer type which can be safely shared between threads
+    /// when "sync" feature is enabled.\
+    /// A 64-bit signed integer type with non-threadsafe interior mutability
+    /// when "sync" feature is not enabled.
+    ///
+    /// Available on targets with 64-bit atomics or with "portable-atomic" feature.
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+    pub type AtomicI64 = core::cell::Cell<i64>;
+
+    /// A 64-bit unsigned integer type which can be safely shared between threads
+    /// when "sync" feature is enabled.\
+    /// A 64-bit unsigned integer type with non-threadsafe interior mutability
+    /// when "sync" feature is not enabled.
+    ///
+    /// Available on targets with 64-bit atomics or with "portable-atomic" feature.
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+    pub type AtomicU64 = core::cell::Cell<u64>;
+
     /// A raw pointer type which can be safely shared between threads
     /// when "sync" feature is enabled.\
     /// A raw pointer type with non-threadsafe interior mutability
@@ -518,6 +1165,32 @@ mod unsync {
     ///
     /// This type has the same in-memory representation as a isize.
     pub type AtomicPtr<T> = core::cell::Cell<*mut T>;
+
+    /// A 128-bit signed integer type which can be safely shared between threads
+    /// when "sync" feature is enabled.\
+    /// A 128-bit signed integer type with non-threadsafe interior mutability
+    /// when "sync" feature is not enabled.
+    ///
+    /// Requires "portable-atomic" feature as there is no 128-bit atomics in `core`.
+    #[cfg(feature = "portable-atomic")]
+    #[cfg_attr(
+        all(doc, feature = "unstable-doc"),
+        doc(cfg(feature = "portable-atomic"))
+    )]
+    pub type AtomicI128 = core::cell::Cell<i128>;
+
+    /// A 128-bit unsigned integer type which can be safely shared between threads
+    /// when "sync" feature is enabled.\
+    /// A 128-bit unsigned integer type with non-threadsafe interior mutability
+    /// when "sync" feature is not enabled.
+    ///
+    /// Requires "portable-atomic" feature as there is no 128-bit atomics in `core`.
+    #[cfg(feature = "portable-atomic")]
+    #[cfg_attr(
+        all(doc, feature = "unstable-doc"),
+        doc(cfg(feature = "portable-atomic"))
+    )]
+    pub type AtomicU128 = core::cell::Cell<u128>;
 }
 
 #[cfg(feature = "sync")]
@@ -526,20 +1199,572 @@ pub use sync::*;
 #[cfg(not(feature = "sync"))]
 pub use unsync::*;
 
-/// Expands to `dyn $traits` with `Send` marker trait
-/// added when "sync" feature is enabled.
+mod atomic;
+
+pub use atomic::*;
+
+mod bounds;
+
+pub use bounds::*;
+
+mod mutex;
+
+pub use mutex::*;
+
+mod rw_lock;
+
+pub use rw_lock::*;
+
+/// `true` when "sync" feature is enabled.
 ///
-/// Expands to `dyn $traits` without `Send` marker trait
-/// added "sync" feature is not enabled.
+/// Cargo unifies features, so this reflects the whole dependency graph,
+/// not only the crate that reads it.
 ///
 /// # Example
+///
 /// ```
-/// # use maybe_sync::{MaybeSend, dyn_maybe_send};
+/// assert_eq!(maybe_sync::SYNC, cfg!(feature = "sync"));
+///
+/// if maybe_sync::SYNC {
+///     println!("multithreaded build");
+/// }
+/// ```
+pub const SYNC: bool = cfg!(feature = "sync");
+
+/// Returns [`SYNC`].\
+/// Convenient where function is expected.
+///
+/// # Example
+///
+/// ```
+/// const CAPACITY: usize = if maybe_sync::is_sync() { 64 } else { 8 };
+/// assert_eq!(CAPACITY, if cfg!(feature = "sync") { 64 } else { 8 });
+/// ```
+///
+/// [`SYNC`]: ./constant.SYNC.html
+pub const fn is_sync() -> bool {
+    SYNC
+}
+
+/// Compile-time check that values of type `T` can be shared using [`Rc`].
+///
+/// [`Rc`] is [`MaybeSend`] and [`MaybeSync`] only when `T: MaybeSend + MaybeSync`.
+/// When "sync" feature is not enabled this always holds,
+/// so code that puts `!Send` or `!Sync` values into [`Rc`] compiles fine
+/// and only fails later, far from the cause, when "sync" feature gets enabled.
+///
+/// Calling this function with payload type of [`Rc`] moves the error
+/// to the call site.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{share_ok, Rc};
+/// struct Payload {
+///     value: u32,
+/// }
+///
+/// const _: () = share_ok::<Payload>();
+///
+/// let rc = Rc::new(Payload { value: 42 });
+/// assert_eq!(rc.value, 42);
+/// ```
+///
+/// [`Rc`]: ./type.Rc.html
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`MaybeSync`]: ./trait.MaybeSync.html
+pub const fn share_ok<T>()
+where
+    T: MaybeSend + MaybeSync + ?Sized,
+{
+}
+
+/// Compile-time check that values of type `T` satisfy [`MaybeSend`] bound.
+///
+/// Useful for named future types returned through [`BoxFuture`].
+/// Without the check, `!Send` future compiles fine when "sync" feature
+/// is not enabled and only fails later, far from the cause.
+/// For unnameable futures, such as `async` blocks, use [`future::boxed`].
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::send_ok, std::{future::Future, pin::Pin, task::{Context, Poll}}};
+/// struct Load {
+///     id: u32,
+/// }
+///
+/// impl Future for Load {
+///     type Output = u32;
+///
+///     fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<u32> {
+///         Poll::Ready(self.id)
+///     }
+/// }
+///
+/// const _: () = send_ok::<Load>();
+/// ```
+///
+/// Non-sendable types fail the check when "sync" feature is enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::send_ok, std::rc::Rc};
+/// struct Load {
+///     cache: Rc<Vec<u8>>,
+/// }
+///
+/// const _: () = send_ok::<Load>();
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`BoxFuture`]: ./type.BoxFuture.html
+/// [`future::boxed`]: ./future/fn.boxed.html
+pub const fn send_ok<T>()
+where
+    T: MaybeSend + ?Sized,
+{
+}
+
+/// Leaks the value returning `'static` reference to it.
+///
+/// Same as `Box::leak(Box::new(value))`
+/// but requires `T` to be [`MaybeSend`] and [`MaybeSync`],
+/// so that the reference can be shared across threads
+/// when "sync" feature is enabled.
+///
+/// # Example
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use maybe_sync::{leak, Mutex};
+/// register(leak(Mutex::new(vec![1, 2])));
+///
+/// fn register(registry: &'static Mutex<Vec<u32>>) {
+///     maybe_sync::if_sync! {{
+///         std::thread::spawn(move || registry.lock().push(3))
+///             .join()
+///             .unwrap();
+///     } else {
+///         registry.lock().push(3);
+///     }}
+///
+///     assert_eq!(*registry.lock(), [1, 2, 3]);
+/// }
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`MaybeSync`]: ./trait.MaybeSync.html
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub fn leak<T>(value: T) -> &'static T
+where
+    T: MaybeSend + MaybeSync + 'static,
+{
+    alloc::boxed::Box::leak(alloc::boxed::Box::new(value))
+}
+
+/// An owned dynamically typed [`Future`] that is never sendable.
+///
+/// A type alias equal to `futures::future::LocalBoxFuture`
+/// regardless of "sync" feature.
+/// Equal to [`BoxFuture`] when "sync" feature is not enabled.
+///
+/// [`Future`]: https://doc.rust-lang.org/core/future/trait.Future.html
+/// [`BoxFuture`]: ./type.BoxFuture.html
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub type LocalBoxFuture<'a, T> =
+    core::pin::Pin<alloc::boxed::Box<dyn core::future::Future<Output = T> + 'a>>;
+
+#[cfg(feature = "alloc")]
+mod append_only_vec;
+
+#[cfg(feature = "alloc")]
+pub use append_only_vec::*;
+
+#[cfg(feature = "alloc")]
+mod any;
+
+#[cfg(feature = "alloc")]
+pub use any::*;
+
+#[cfg(feature = "futures-io")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "futures-io")))]
+mod async_io;
+
+#[cfg(feature = "futures-io")]
+pub use async_io::*;
+
+#[cfg(feature = "alloc")]
+mod boxed;
+
+#[cfg(feature = "alloc")]
+pub use boxed::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "std")))]
+mod cache;
+
+#[cfg(feature = "std")]
+pub use cache::*;
+
+#[cfg(feature = "alloc")]
+mod callbacks;
+
+#[cfg(feature = "alloc")]
+pub use callbacks::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "std")))]
+mod io;
+
+#[cfg(feature = "std")]
+pub use io::*;
+
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "macros")))]
+pub use maybe_sync_macros::{boxed_future, define_dyn_alias};
+
+/// When "sync" feature is enabled implementations of `&self` methods
+/// require `Self: Sync`, so the returned future can be sent.
+///
+#[cfg_attr(feature = "sync", doc = "```compile_fail,E0277")]
+#[cfg_attr(not(feature = "sync"), doc = "```")]
+/// # use {maybe_sync::{future::now_or_never, maybe_async_trait}, std::cell::Cell};
+/// #[maybe_async_trait]
+/// trait Storage {
+///     async fn load(&self, key: &str) -> Option<u32>;
+/// }
+///
+/// struct Counting {
+///     hits: Cell<u32>,
+/// }
+///
+/// #[maybe_async_trait]
+/// impl Storage for Counting {
+///     async fn load(&self, key: &str) -> Option<u32> {
+///         self.hits.set(self.hits.get() + 1);
+///         key.parse().ok()
+///     }
+/// }
+///
+/// let storage = Counting { hits: Cell::new(0) };
+/// assert_eq!(now_or_never(storage.load("1")), Some(Some(1)));
+/// ```
+///
+/// Arguments captured by the future must be sendable too.
+///
+#[cfg_attr(
+    any(feature = "sync", feature = "strict"),
+    doc = "```compile_fail,E0277"
+)]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::{future::now_or_never, maybe_async_trait}, std::{fmt::Debug, rc::Rc}};
+/// #[maybe_async_trait]
+/// trait Log {
+///     async fn write(&self, entry: impl Debug) -> String;
+/// }
+///
+/// struct Format;
+///
+/// #[maybe_async_trait]
+/// impl Log for Format {
+///     async fn write(&self, entry: impl Debug) -> String {
+///         format!("{:?}", entry)
+///     }
+/// }
+///
+/// assert_eq!(now_or_never(Format.write(Rc::new(1))), Some("1".to_owned()));
+/// ```
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "macros")))]
+pub use maybe_sync_macros::maybe_async_trait;
+
+/// Implementations must satisfy the bounds when "sync" feature is enabled.
+///
+#[cfg_attr(
+    any(feature = "sync", feature = "strict"),
+    doc = "```compile_fail,E0277"
+)]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::{maybe_send_bounds, BoxFuture}, std::rc::Rc};
+/// #[maybe_send_bounds(sync)]
+/// trait Loader<K> {
+///     type Asset;
+///
+///     fn load(&self, key: K) -> BoxFuture<'_, Option<Self::Asset>>;
+/// }
+///
+/// struct Strings(Vec<String>);
+///
+/// impl Loader<usize> for Strings {
+///     // Not sendable.
+///     type Asset = Rc<str>;
+///
+///     fn load(&self, key: usize) -> BoxFuture<'_, Option<Rc<str>>> {
+///         let asset = self.0.get(key).map(|s| Rc::from(s.as_str()));
+///         Box::pin(async move { asset })
+///     }
+/// }
+/// ```
+///
+/// Implementing type itself must be shareable, so trait objects are.
+///
+#[cfg_attr(
+    any(feature = "sync", feature = "strict"),
+    doc = "```compile_fail,E0277"
+)]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::maybe_send_bounds, std::cell::Cell};
+/// #[maybe_send_bounds(sync)]
+/// trait Counter {
+///     fn next(&self) -> u32;
+/// }
+///
+/// struct Local(Cell<u32>);
+///
+/// impl Counter for Local {
+///     fn next(&self) -> u32 {
+///         self.0.replace(self.0.get() + 1)
+///     }
+/// }
+///
+/// let counter: Box<dyn Counter> = Box::new(Local(Cell::new(0)));
+/// assert_eq!(counter.next(), 0);
+/// ```
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "macros")))]
+pub use maybe_sync_macros::maybe_send_bounds;
+
+/// Non-sendable types are rejected when "sync" feature is enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::maybe_bounds, std::rc::Rc};
+/// maybe_bounds! {
+///     fn spawn<T>(value: T) -> T where T: Clone {
+///         value
+///     }
+/// }
+///
+/// spawn(Rc::new(42));
+/// ```
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "macros")))]
+pub use maybe_sync_macros::maybe_bounds;
+
+/// Compile error points at the field that is not sendable
+/// when "sync" feature is enabled.
+///
+#[cfg_attr(
+    any(feature = "sync", feature = "strict"),
+    doc = "```compile_fail,E0277"
+)]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use maybe_sync::AssertMaybeSend;
+/// #[derive(AssertMaybeSend)]
+/// struct Scene<T> {
+///     nodes: Vec<T>,
+///     cache: std::rc::Rc<Vec<u32>>,
+/// }
+/// ```
+///
+/// Local fields can be skipped.
+///
+/// ```
+/// # use maybe_sync::AssertMaybeSend;
+/// #[derive(AssertMaybeSend)]
+/// struct Scene<T> {
+///     nodes: Vec<T>,
+///     #[assert_maybe(skip)]
+///     cache: std::rc::Rc<Vec<u32>>,
+/// }
+/// ```
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "macros")))]
+pub use maybe_sync_macros::AssertMaybeSend;
+
+/// Compile error points at the field that is not shareable
+/// when "sync" feature is enabled.
+///
+#[cfg_attr(
+    any(feature = "sync", feature = "strict"),
+    doc = "```compile_fail,E0277"
+)]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use maybe_sync::AssertMaybeSync;
+/// #[derive(AssertMaybeSync)]
+/// struct Counter {
+///     name: String,
+///     value: std::cell::Cell<u32>,
+/// }
+/// ```
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "macros")))]
+pub use maybe_sync_macros::AssertMaybeSync;
+
+mod lock_step;
+
+pub use lock_step::*;
+
+#[cfg(feature = "alloc")]
+mod mapped_rc;
+
+#[cfg(feature = "alloc")]
+pub use mapped_rc::*;
+
+#[cfg(feature = "alloc")]
+mod observers;
+
+#[cfg(feature = "alloc")]
+pub use observers::*;
+
+mod par_iter;
+
+pub use par_iter::*;
+
+#[cfg(feature = "alloc")]
+mod pool;
+
+#[cfg(feature = "alloc")]
+pub use pool::*;
+
+#[cfg(feature = "alloc")]
+mod rc_ref;
+
+#[cfg(feature = "alloc")]
+pub use rc_ref::*;
+
+#[cfg(feature = "alloc")]
+mod shared;
+
+#[cfg(feature = "alloc")]
+pub use shared::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "std")))]
+mod spawner;
+
+#[cfg(feature = "std")]
+pub use spawner::*;
+
+#[cfg(feature = "alloc")]
+mod small_future;
+
+#[cfg(feature = "alloc")]
+pub use small_future::*;
+
+#[cfg(feature = "futures")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "futures")))]
+mod stream;
+
+#[cfg(feature = "futures")]
+pub use stream::*;
+
+#[cfg(feature = "alloc")]
+mod weak_value_map;
+
+#[cfg(feature = "alloc")]
+pub use weak_value_map::*;
+
+/// Expands to `dyn $traits` with `Send` marker trait
+/// added when "sync" feature is enabled.
+///
+/// Expands to `dyn $traits` without `Send` marker trait
+/// added "sync" feature is not enabled.
+///
+/// # Example
+/// ```
+/// # use maybe_sync::{MaybeSend, dyn_maybe_send};
 /// fn foo<T: MaybeSend>(_: T) {}
 /// // `x` will implement `MaybeSend` whether "sync" feature is enabled or not.
 /// let x: Box<dyn_maybe_send!(std::future::Future<Output = u32>)> = Box::new(async move { 42 });
 /// foo(x);
 /// ```
+///
+/// Marker trait is appended after all given bounds,
+/// which parses regardless of their order.
+/// Lifetimes, higher-ranked bounds, associated type bindings
+/// and explicit auto traits are accepted anywhere in the list.
+///
+/// ```
+/// # use {maybe_sync::{MaybeSend, dyn_maybe_send}, std::{fmt::Debug, future::Future}};
+/// fn foo<T: MaybeSend + ?Sized>(_: &T) {}
+///
+/// let a: Box<dyn_maybe_send!(Future<Output = u32> + 'static + Unpin)> =
+///     Box::new(std::future::ready(1));
+/// let b: Box<dyn_maybe_send!(for<'a> Fn(&'a str) -> bool + 'static)> =
+///     Box::new(|s: &str| s.is_empty());
+/// let c: Box<dyn_maybe_send!('static + Unpin + Fn(&str) -> &str)> = Box::new(|s: &str| s);
+///
+/// // Explicit markers are allowed too, duplicates are harmless.
+/// let d: Box<dyn_maybe_send!(Debug + Send + Sync)> = Box::new(1);
+///
+/// foo(&*a);
+/// foo(&*b);
+/// foo(&*c);
+/// foo(&*d);
+/// assert!(b("") && c("x") == "x");
+/// ```
+///
+/// Type parameters of generic type aliases and associated types
+/// can be used in the bounds, including aliases declared by other macros.
+///
+/// ```
+/// # use maybe_sync::{dyn_maybe_send, send_ok};
+/// type Map<T> = Box<dyn_maybe_send!(Fn(T) -> T)>;
+/// type Visit<'a, T> = Box<dyn_maybe_send!(FnMut(&'a T) + 'a)>;
+///
+/// macro_rules! handler {
+///     ($name:ident<$t:ident>) => {
+///         type $name<$t> = Box<dyn_maybe_send!(Fn(&$t) -> bool)>;
+///     };
+/// }
+/// handler!(Filter<T>);
+///
+/// trait Stage {
+///     type Map<T: 'static>;
+///
+///     fn map<T: Clone + 'static>() -> Self::Map<T>;
+/// }
+///
+/// struct Identity;
+///
+/// impl Stage for Identity {
+///     type Map<T: 'static> = Box<dyn_maybe_send!(Fn(T) -> T)>;
+///
+///     fn map<T: Clone + 'static>() -> Self::Map<T> {
+///         Box::new(|x| x)
+///     }
+/// }
+///
+/// let double: Map<u32> = Box::new(|x| x * 2);
+/// let six = double(3);
+/// let mut sum = 0;
+/// let mut visit: Visit<'_, u32> = Box::new(|x| sum += x);
+/// let even: Filter<u32> = Box::new(|x| x % 2 == 0);
+///
+/// visit(&six);
+/// drop(visit);
+/// assert_eq!(sum, 6);
+/// assert!(even(&sum));
+/// assert_eq!(Identity::map::<u8>()(7), 7);
+///
+/// send_ok::<Map<u32>>();
+/// send_ok::<Filter<u32>>();
+/// send_ok::<<Identity as Stage>::Map<u32>>();
+/// ```
+///
+/// Empty or malformed bounds are rejected.
+///
+/// ```compile_fail
+/// let x: Box<maybe_sync::dyn_maybe_send!()> = Box::new(42);
+/// ```
+///
+/// ```compile_fail
+/// let x: Box<maybe_sync::dyn_maybe_send!(std::fmt::Debug + + 'static)> = Box::new(42);
+/// ```
 #[cfg(feature = "sync")]
 #[macro_export]
 macro_rules! dyn_maybe_send {
@@ -555,13 +1780,99 @@ macro_rules! dyn_maybe_send {
 /// added "sync" feature is not enabled.
 ///
 /// # Example
-/// ```
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
 /// # use maybe_sync::{MaybeSend, dyn_maybe_send};
 /// fn foo<T: MaybeSend>(_: T) {}
 /// // `x` will implement `MaybeSend` whether "sync" feature is enabled or not.
 /// let x: Box<dyn_maybe_send!(std::future::Future<Output = u32>)> = Box::new(async move { 42 });
 /// foo(x);
 /// ```
+///
+/// Marker trait is appended after all given bounds,
+/// which parses regardless of their order.
+/// Lifetimes, higher-ranked bounds, associated type bindings
+/// and explicit auto traits are accepted anywhere in the list.
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use {maybe_sync::{MaybeSend, dyn_maybe_send}, std::{fmt::Debug, future::Future}};
+/// fn foo<T: MaybeSend + ?Sized>(_: &T) {}
+///
+/// let a: Box<dyn_maybe_send!(Future<Output = u32> + 'static + Unpin)> =
+///     Box::new(std::future::ready(1));
+/// let b: Box<dyn_maybe_send!(for<'a> Fn(&'a str) -> bool + 'static)> =
+///     Box::new(|s: &str| s.is_empty());
+/// let c: Box<dyn_maybe_send!('static + Unpin + Fn(&str) -> &str)> = Box::new(|s: &str| s);
+///
+/// // Explicit markers are allowed too, duplicates are harmless.
+/// let d: Box<dyn_maybe_send!(Debug + Send + Sync)> = Box::new(1);
+///
+/// foo(&*a);
+/// foo(&*b);
+/// foo(&*c);
+/// foo(&*d);
+/// assert!(b("") && c("x") == "x");
+/// ```
+///
+/// Type parameters of generic type aliases and associated types
+/// can be used in the bounds, including aliases declared by other macros.
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use maybe_sync::{dyn_maybe_send, send_ok};
+/// type Map<T> = Box<dyn_maybe_send!(Fn(T) -> T)>;
+/// type Visit<'a, T> = Box<dyn_maybe_send!(FnMut(&'a T) + 'a)>;
+///
+/// macro_rules! handler {
+///     ($name:ident<$t:ident>) => {
+///         type $name<$t> = Box<dyn_maybe_send!(Fn(&$t) -> bool)>;
+///     };
+/// }
+/// handler!(Filter<T>);
+///
+/// trait Stage {
+///     type Map<T: 'static>;
+///
+///     fn map<T: Clone + 'static>() -> Self::Map<T>;
+/// }
+///
+/// struct Identity;
+///
+/// impl Stage for Identity {
+///     type Map<T: 'static> = Box<dyn_maybe_send!(Fn(T) -> T)>;
+///
+///     fn map<T: Clone + 'static>() -> Self::Map<T> {
+///         Box::new(|x| x)
+///     }
+/// }
+///
+/// let double: Map<u32> = Box::new(|x| x * 2);
+/// let six = double(3);
+/// let mut sum = 0;
+/// let mut visit: Visit<'_, u32> = Box::new(|x| sum += x);
+/// let even: Filter<u32> = Box::new(|x| x % 2 == 0);
+///
+/// visit(&six);
+/// drop(visit);
+/// assert_eq!(sum, 6);
+/// assert!(even(&sum));
+/// assert_eq!(Identity::map::<u8>()(7), 7);
+///
+/// send_ok::<Map<u32>>();
+/// send_ok::<Filter<u32>>();
+/// send_ok::<<Identity as Stage>::Map<u32>>();
+/// ```
+///
+/// Empty or malformed bounds are rejected.
+///
+/// ```compile_fail
+/// let x: Box<maybe_sync::dyn_maybe_send!()> = Box::new(42);
+/// ```
+///
+/// ```compile_fail
+/// let x: Box<maybe_sync::dyn_maybe_send!(std::fmt::Debug + + 'static)> = Box::new(42);
+/// ```
 #[cfg(not(feature = "sync"))]
 #[macro_export]
 macro_rules! dyn_maybe_send {
@@ -585,6 +1896,40 @@ macro_rules! dyn_maybe_send {
 /// // `x` will implement `MaybeSync` whether "sync" feature is enabled or not.
 /// foo(x);
 /// ```
+///
+/// Lifetimes, higher-ranked bounds, associated type bindings
+/// and explicit auto traits are accepted anywhere in the list,
+/// as well as type parameters of generic type aliases.
+///
+/// ```
+/// # use {maybe_sync::{dyn_maybe_sync, MaybeSync}, std::fmt::Debug};
+/// fn sync_ok<T: MaybeSync + ?Sized>() {}
+///
+/// type Check<'a, T> = dyn_maybe_sync!(for<'b> Fn(&'b T) -> bool + 'a);
+/// type Items = dyn_maybe_sync!('static + AsRef<[u32]>);
+///
+/// let even: &Check<'_, u32> = &|x: &u32| x % 2 == 0;
+/// let items: &Items = &vec![1, 2, 3];
+/// let debug: &dyn_maybe_sync!(Debug + Send + Sync) = &1;
+///
+/// assert!(even(&2));
+/// assert_eq!(items.as_ref(), [1, 2, 3]);
+/// assert_eq!(format!("{:?}", debug), "1");
+///
+/// sync_ok::<Check<'static, u32>>();
+/// sync_ok::<Items>();
+/// sync_ok::<dyn_maybe_sync!(Iterator<Item = u32> + 'static)>();
+/// ```
+///
+/// Empty or malformed bounds are rejected.
+///
+/// ```compile_fail
+/// let x: &maybe_sync::dyn_maybe_sync!() = &"qwerty";
+/// ```
+///
+/// ```compile_fail
+/// let x: &maybe_sync::dyn_maybe_sync!(AsRef<str> + + std::fmt::Debug) = &"qwerty";
+/// ```
 #[cfg(feature = "sync")]
 #[macro_export]
 macro_rules! dyn_maybe_sync {
@@ -600,13 +1945,49 @@ macro_rules! dyn_maybe_sync {
 /// added "sync" feature is not enabled.
 ///
 /// # Example
-/// ```
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
 /// # use maybe_sync::{MaybeSync, dyn_maybe_sync};
 /// fn foo<T: MaybeSync + ?Sized>(_: &T) {}
 /// // `x` will implement `MaybeSync` whether "sync" feature is enabled or not.
 /// let x: &dyn_maybe_sync!(AsRef<str>) = &"qwerty";
 /// foo(x);
 /// ```
+///
+/// Lifetimes, higher-ranked bounds, associated type bindings
+/// and explicit auto traits are accepted anywhere in the list,
+/// as well as type parameters of generic type aliases.
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use {maybe_sync::{dyn_maybe_sync, MaybeSync}, std::fmt::Debug};
+/// fn sync_ok<T: MaybeSync + ?Sized>() {}
+///
+/// type Check<'a, T> = dyn_maybe_sync!(for<'b> Fn(&'b T) -> bool + 'a);
+/// type Items = dyn_maybe_sync!('static + AsRef<[u32]>);
+///
+/// let even: &Check<'_, u32> = &|x: &u32| x % 2 == 0;
+/// let items: &Items = &vec![1, 2, 3];
+/// let debug: &dyn_maybe_sync!(Debug + Send + Sync) = &1;
+///
+/// assert!(even(&2));
+/// assert_eq!(items.as_ref(), [1, 2, 3]);
+/// assert_eq!(format!("{:?}", debug), "1");
+///
+/// sync_ok::<Check<'static, u32>>();
+/// sync_ok::<Items>();
+/// sync_ok::<dyn_maybe_sync!(Iterator<Item = u32> + 'static)>();
+/// ```
+///
+/// Empty or malformed bounds are rejected.
+///
+/// ```compile_fail
+/// let x: &maybe_sync::dyn_maybe_sync!() = &"qwerty";
+/// ```
+///
+/// ```compile_fail
+/// let x: &maybe_sync::dyn_maybe_sync!(AsRef<str> + + std::fmt::Debug) = &"qwerty";
+/// ```
 #[cfg(not(feature = "sync"))]
 #[macro_export]
 macro_rules! dyn_maybe_sync {
@@ -629,6 +2010,38 @@ macro_rules! dyn_maybe_sync {
 /// let x: &dyn_maybe_send_sync!(AsRef<str>) = &"qwerty";
 /// foo(x);
 /// ```
+///
+/// Lifetimes, higher-ranked bounds, associated type bindings
+/// and explicit auto traits are accepted anywhere in the list,
+/// as well as type parameters of generic type aliases.
+///
+/// ```
+/// # use {maybe_sync::{dyn_maybe_send_sync, share_ok}, std::fmt::Debug};
+/// type Check<'a, T> = Box<dyn_maybe_send_sync!(for<'b> Fn(&'b T) -> bool + 'a)>;
+/// type Items = dyn_maybe_send_sync!('static + AsRef<[u32]>);
+///
+/// let even: Check<'_, u32> = Box::new(|x: &u32| x % 2 == 0);
+/// let items: &Items = &vec![1, 2, 3];
+/// let debug: &dyn_maybe_send_sync!(Debug + Send) = &1;
+///
+/// assert!(even(&2));
+/// assert_eq!(items.as_ref(), [1, 2, 3]);
+/// assert_eq!(format!("{:?}", debug), "1");
+///
+/// share_ok::<Check<'static, u32>>();
+/// share_ok::<Items>();
+/// share_ok::<dyn_maybe_send_sync!(Iterator<Item = u32> + 'static)>();
+/// ```
+///
+/// Empty or malformed bounds are rejected.
+///
+/// ```compile_fail
+/// let x: &maybe_sync::dyn_maybe_send_sync!() = &"qwerty";
+/// ```
+///
+/// ```compile_fail
+/// let x: &maybe_sync::dyn_maybe_send_sync!(AsRef<str> + + std::fmt::Debug) = &"qwerty";
+/// ```
 #[cfg(feature = "sync")]
 #[macro_export]
 macro_rules! dyn_maybe_send_sync {
@@ -637,24 +2050,942 @@ macro_rules! dyn_maybe_send_sync {
     };
 }
 
-/// Expands to `dyn $traits` with `Sync` marker trait
+/// Expands to `dyn $traits` with `Send` and `Sync` marker trait
 /// added when "sync" feature is enabled.
 ///
-/// Expands to `dyn $traits` without `Sync` marker trait
+/// Expands to `dyn $traits` without `Send` and `Sync` marker trait
 /// added "sync" feature is not enabled.
 ///
 /// # Example
-/// ```
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
 /// # use maybe_sync::{MaybeSend, MaybeSync, dyn_maybe_send_sync};
 /// fn foo<T: MaybeSend + MaybeSync + ?Sized>(_: &T) {}
 /// // `x` will implement `MaybeSend` and `MaybeSync` whether "sync" feature is enabled or not.
 /// let x: &dyn_maybe_send_sync!(AsRef<str>) = &"qwerty";
 /// foo(x);
 /// ```
-#[cfg(not(feature = "sync"))]
-#[macro_export]
-macro_rules! dyn_maybe_send_sync {
+///
+/// Lifetimes, higher-ranked bounds, associated type bindings
+/// and explicit auto traits are accepted anywhere in the list,
+/// as well as type parameters of generic type aliases.
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use {maybe_sync::{dyn_maybe_send_sync, share_ok}, std::fmt::Debug};
+/// type Check<'a, T> = Box<dyn_maybe_send_sync!(for<'b> Fn(&'b T) -> bool + 'a)>;
+/// type Items = dyn_maybe_send_sync!('static + AsRef<[u32]>);
+///
+/// let even: Check<'_, u32> = Box::new(|x: &u32| x % 2 == 0);
+/// let items: &Items = &vec![1, 2, 3];
+/// let debug: &dyn_maybe_send_sync!(Debug + Send) = &1;
+///
+/// assert!(even(&2));
+/// assert_eq!(items.as_ref(), [1, 2, 3]);
+/// assert_eq!(format!("{:?}", debug), "1");
+///
+/// share_ok::<Check<'static, u32>>();
+/// share_ok::<Items>();
+/// share_ok::<dyn_maybe_send_sync!(Iterator<Item = u32> + 'static)>();
+/// ```
+///
+/// Empty or malformed bounds are rejected.
+///
+/// ```compile_fail
+/// let x: &maybe_sync::dyn_maybe_send_sync!() = &"qwerty";
+/// ```
+///
+/// ```compile_fail
+/// let x: &maybe_sync::dyn_maybe_send_sync!(AsRef<str> + + std::fmt::Debug) = &"qwerty";
+/// ```
+#[cfg(not(feature = "sync"))]
+#[macro_export]
+macro_rules! dyn_maybe_send_sync {
     ($($traits:tt)+) => {
         dyn $($traits)+
     };
 }
+
+/// Expands to `impl $traits` with `Send` marker trait
+/// added when "sync" feature is enabled.
+///
+/// Expands to `impl $traits` without `Send` marker trait
+/// added "sync" feature is not enabled.
+///
+/// Usable in return position of free functions and trait impls
+/// to avoid boxing where [`MaybeSend`] is expected.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{impl_maybe_send, MaybeSend}, std::future::Future};
+/// fn spawn<F: Future + MaybeSend>(_: F) {}
+///
+/// struct Asset;
+///
+/// trait Loader {
+///     fn load(&self) -> impl Future<Output = Asset> + '_;
+/// }
+///
+/// struct Disk;
+///
+/// impl Loader for Disk {
+///     fn load(&self) -> impl_maybe_send!(Future<Output = Asset> + '_) {
+///         async { Asset }
+///     }
+/// }
+///
+/// fn load_default() -> impl_maybe_send!(Future<Output = Asset>) {
+///     async { Asset }
+/// }
+///
+/// spawn(Disk.load());
+/// spawn(load_default());
+/// ```
+///
+/// Non-sendable future is accepted only when "sync" feature is not enabled.
+///
+#[cfg_attr(feature = "sync", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "sync"), doc = "```")]
+/// # use {maybe_sync::impl_maybe_send, std::{future::Future, rc::Rc}};
+/// fn load() -> impl_maybe_send!(Future<Output = u32>) {
+///     let cache = Rc::new(42);
+///     async move { *cache }
+/// }
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+#[cfg(feature = "sync")]
+#[macro_export]
+macro_rules! impl_maybe_send {
+    ($($traits:tt)+) => {
+        impl $($traits)+ + Send
+    };
+}
+
+/// Expands to `impl $traits` with `Send` marker trait
+/// added when "sync" feature is enabled.
+///
+/// Expands to `impl $traits` without `Send` marker trait
+/// added "sync" feature is not enabled.
+///
+/// Usable in return position of free functions and trait impls
+/// to avoid boxing where [`MaybeSend`] is expected.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{impl_maybe_send, MaybeSend}, std::future::Future};
+/// fn spawn<F: Future + MaybeSend>(_: F) {}
+///
+/// struct Asset;
+///
+/// trait Loader {
+///     fn load(&self) -> impl Future<Output = Asset> + '_;
+/// }
+///
+/// struct Disk;
+///
+/// impl Loader for Disk {
+///     fn load(&self) -> impl_maybe_send!(Future<Output = Asset> + '_) {
+///         async { Asset }
+///     }
+/// }
+///
+/// fn load_default() -> impl_maybe_send!(Future<Output = Asset>) {
+///     async { Asset }
+/// }
+///
+/// spawn(Disk.load());
+/// spawn(load_default());
+/// ```
+///
+/// Non-sendable future is accepted only when "sync" feature is not enabled.
+///
+#[cfg_attr(feature = "sync", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "sync"), doc = "```")]
+/// # use {maybe_sync::impl_maybe_send, std::{future::Future, rc::Rc}};
+/// fn load() -> impl_maybe_send!(Future<Output = u32>) {
+///     let cache = Rc::new(42);
+///     async move { *cache }
+/// }
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+#[cfg(not(feature = "sync"))]
+#[macro_export]
+macro_rules! impl_maybe_send {
+    ($($traits:tt)+) => {
+        impl $($traits)+
+    };
+}
+
+/// Expands to `impl $traits` with `Send` and `Sync` marker traits
+/// added when "sync" feature is enabled.
+///
+/// Expands to `impl $traits` without `Send` and `Sync` marker traits
+/// added "sync" feature is not enabled.
+///
+/// Usable in return position of free functions and trait impls
+/// to avoid boxing where [`MaybeSend`] and [`MaybeSync`] are expected.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{impl_maybe_send_sync, MaybeSend, MaybeSync}, std::future::Future};
+/// fn spawn<F: Future + MaybeSend + MaybeSync>(_: F) {}
+///
+/// struct Asset;
+///
+/// trait Loader {
+///     fn load(&self) -> impl Future<Output = Asset> + '_;
+/// }
+///
+/// struct Disk;
+///
+/// impl Loader for Disk {
+///     fn load(&self) -> impl_maybe_send_sync!(Future<Output = Asset> + '_) {
+///         async { Asset }
+///     }
+/// }
+///
+/// fn load_default() -> impl_maybe_send_sync!(Future<Output = Asset>) {
+///     async { Asset }
+/// }
+///
+/// spawn(Disk.load());
+/// spawn(load_default());
+/// ```
+///
+/// Non-sendable future is accepted only when "sync" feature is not enabled.
+///
+#[cfg_attr(feature = "sync", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "sync"), doc = "```")]
+/// # use {maybe_sync::impl_maybe_send_sync, std::{future::Future, rc::Rc}};
+/// fn load() -> impl_maybe_send_sync!(Future<Output = u32>) {
+///     let cache = Rc::new(42);
+///     async move { *cache }
+/// }
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`MaybeSync`]: ./trait.MaybeSync.html
+#[cfg(feature = "sync")]
+#[macro_export]
+macro_rules! impl_maybe_send_sync {
+    ($($traits:tt)+) => {
+        impl $($traits)+ + Send + Sync
+    };
+}
+
+/// Expands to `impl $traits` with `Send` and `Sync` marker traits
+/// added when "sync" feature is enabled.
+///
+/// Expands to `impl $traits` without `Send` and `Sync` marker traits
+/// added "sync" feature is not enabled.
+///
+/// Usable in return position of free functions and trait impls
+/// to avoid boxing where [`MaybeSend`] and [`MaybeSync`] are expected.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{impl_maybe_send_sync, MaybeSend, MaybeSync}, std::future::Future};
+/// fn spawn<F: Future + MaybeSend + MaybeSync>(_: F) {}
+///
+/// struct Asset;
+///
+/// trait Loader {
+///     fn load(&self) -> impl Future<Output = Asset> + '_;
+/// }
+///
+/// struct Disk;
+///
+/// impl Loader for Disk {
+///     fn load(&self) -> impl_maybe_send_sync!(Future<Output = Asset> + '_) {
+///         async { Asset }
+///     }
+/// }
+///
+/// fn load_default() -> impl_maybe_send_sync!(Future<Output = Asset>) {
+///     async { Asset }
+/// }
+///
+/// spawn(Disk.load());
+/// spawn(load_default());
+/// ```
+///
+/// Non-sendable future is accepted only when "sync" feature is not enabled.
+///
+#[cfg_attr(feature = "sync", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "sync"), doc = "```")]
+/// # use {maybe_sync::impl_maybe_send_sync, std::{future::Future, rc::Rc}};
+/// fn load() -> impl_maybe_send_sync!(Future<Output = u32>) {
+///     let cache = Rc::new(42);
+///     async move { *cache }
+/// }
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`MaybeSync`]: ./trait.MaybeSync.html
+#[cfg(not(feature = "sync"))]
+#[macro_export]
+macro_rules! impl_maybe_send_sync {
+    ($($traits:tt)+) => {
+        impl $($traits)+
+    };
+}
+
+/// Declares trait that bundles given bounds with [`MaybeSend`] and [`MaybeSync`]
+/// and implements it for all types satisfying them.
+///
+/// `maybe_trait_alias!(pub Resource: Any + 'static);` expands to
+///
+/// ```text
+/// pub trait Resource: Any + 'static + MaybeSend + MaybeSync {}
+/// impl<T> Resource for T where T: Any + 'static + MaybeSend + MaybeSync + ?Sized {}
+/// ```
+///
+/// Prefix `send:` or `sync:` to add only [`MaybeSend`] or only [`MaybeSync`].\
+/// Attributes, including doc comments, are applied to the trait.
+/// Type and lifetime parameters may be declared after the name.
+///
+/// Blanket implementation covers every type with the bounds,
+/// so the trait cannot be implemented manually.
+///
+/// # Example
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use {maybe_sync::{maybe_trait_alias, Rc}, std::any::Any};
+/// maybe_trait_alias! {
+///     /// Values that can be stored in the world.
+///     pub Resource: Any + 'static
+/// }
+///
+/// maybe_trait_alias!(send: Handler<E>: FnMut(&E) -> bool);
+///
+/// fn insert(resources: &mut Vec<Box<dyn Resource>>, value: impl Resource) {
+///     resources.push(Box::new(value));
+/// }
+///
+/// fn dispatch<E>(handlers: &mut [Box<dyn Handler<E>>], event: &E) -> usize {
+///     handlers.iter_mut().map(|h| h(event)).filter(|&hit| hit).count()
+/// }
+///
+/// let mut resources = Vec::new();
+/// insert(&mut resources, 42u32);
+/// insert(&mut resources, Rc::new("shared"));
+/// assert_eq!(resources.len(), 2);
+///
+/// let mut handlers: Vec<Box<dyn Handler<u32>>> = vec![Box::new(|e| *e > 1), Box::new(|e| *e > 5)];
+/// assert_eq!(dispatch(&mut handlers, &3), 1);
+/// ```
+///
+/// Non-sendable types do not implement the trait when "sync" feature is enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::maybe_trait_alias, std::{any::Any, rc::Rc}};
+/// maybe_trait_alias!(pub Resource: Any);
+///
+/// fn insert(_: impl Resource) {}
+/// insert(Rc::new(42));
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`MaybeSync`]: ./trait.MaybeSync.html
+#[macro_export]
+macro_rules! maybe_trait_alias {
+    (send: $($alias:tt)*) => {
+        $crate::maybe_trait_alias!(@alias [$crate::MaybeSend] $($alias)*);
+    };
+    (sync: $($alias:tt)*) => {
+        $crate::maybe_trait_alias!(@alias [$crate::MaybeSync] $($alias)*);
+    };
+    (@alias [$($m:tt)*] $(#[$attr:meta])* $vis:vis $name:ident $(<$($param:tt),+ $(,)?>)? : $($bounds:tt)+) => {
+        $(#[$attr])*
+        $vis trait $name $(<$($param),+>)?: $($bounds)+ + $($m)* {}
+
+        impl<$($($param,)+)? __T> $name $(<$($param),+>)? for __T
+        where
+            __T: $($bounds)+ + $($m)* + ?Sized,
+        {
+        }
+    };
+    ($($alias:tt)*) => {
+        $crate::maybe_trait_alias!(@alias [$crate::MaybeSend + $crate::MaybeSync] $($alias)*);
+    };
+}
+
+/// Expands to boxed `FnMut` trait object with given call signature.\
+/// Boxed closure is sendable when "sync" feature is enabled.
+///
+/// `maybe_box_fnmut!((A, B) -> R)` expands to `Box<dyn FnMut(A, B) -> R + Send>`
+/// when "sync" feature is enabled.\
+/// `maybe_box_fnmut!((A, B) -> R)` expands to `Box<dyn FnMut(A, B) -> R>`
+/// when "sync" feature is not enabled.
+///
+/// # Example
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use maybe_sync::{maybe_box_fnmut, MaybeSend};
+/// struct Button {
+///     on_click: maybe_box_fnmut!((&str) -> bool),
+/// }
+///
+/// fn foo<T: MaybeSend>(_: T) {}
+///
+/// let mut button = Button { on_click: Box::new(|name| name == "ok") };
+/// assert!((button.on_click)("ok"));
+/// // `button` will implement `MaybeSend` whether "sync" feature is enabled or not.
+/// foo(button);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+#[macro_export]
+macro_rules! maybe_box_fnmut {
+    (($($args:ty),* $(,)?) $(-> $ret:ty)?) => {
+        $crate::__private::Box<$crate::dyn_maybe_send!(FnMut($($args),*) $(-> $ret)?)>
+    };
+}
+
+/// Boxes a future into [`BoxFuture`]
+/// or a value into boxed trait object that is sendable when "sync" feature is enabled.
+///
+/// `boxed_maybe!(future)` is the same as [`future::boxed`].\
+/// `boxed_maybe!(dyn Trait, value)` expands to `Box<dyn_maybe_send!(Trait)>`
+/// created from the value.
+///
+/// The box is explicitly typed, so unsized coercion happens
+/// even where type inference would fail to find the target type,
+/// e.g. when boxed closure is pushed into a vector or returned from a match.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{boxed_maybe, dyn_maybe_send, future::now_or_never, BoxFuture};
+/// let value = 21;
+/// let future: BoxFuture<'_, u32> = boxed_maybe!(async move { value * 2 });
+/// assert_eq!(now_or_never(future), Some(42));
+///
+/// let handlers = vec![
+///     boxed_maybe!(dyn Fn(u32) -> u32, |x| x + 1),
+///     boxed_maybe!(dyn Fn(u32) -> u32, |x| x * 2),
+/// ];
+/// assert_eq!(handlers.iter().map(|f| f(10)).collect::<Vec<_>>(), [11, 20]);
+///
+/// trait Convert<A, B> {
+///     fn convert(&self, a: A) -> B;
+/// }
+///
+/// impl Convert<u8, u32> for () {
+///     fn convert(&self, a: u8) -> u32 {
+///         a.into()
+///     }
+/// }
+///
+/// let converter: Box<dyn_maybe_send!(Convert<u8, u32>)> = boxed_maybe!(dyn Convert<u8, u32>, ());
+/// assert_eq!(converter.convert(7), 7);
+/// ```
+///
+/// Works from inside other macros.
+///
+/// ```
+/// # use maybe_sync::{boxed_maybe, future::now_or_never};
+/// macro_rules! task {
+///     ($value:expr) => {
+///         boxed_maybe!(async move { $value })
+///     };
+/// }
+///
+/// macro_rules! callback {
+///     ($boxed:ident => $body:expr) => {
+///         boxed_maybe!(dyn Fn(u32) -> u32, |$boxed| $body)
+///     };
+/// }
+///
+/// let boxed = 1;
+/// assert_eq!(now_or_never(task!(boxed + 1)), Some(2));
+/// assert_eq!(callback!(boxed => boxed * 3)(2), 6);
+/// ```
+///
+/// Non-sendable values are rejected when "sync" feature is enabled.
+///
+#[cfg_attr(feature = "sync", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "sync"), doc = "```")]
+/// # use {maybe_sync::boxed_maybe, std::rc::Rc};
+/// let counter = Rc::new(1);
+/// let read = boxed_maybe!(dyn Fn() -> u32, move || *counter);
+/// assert_eq!(read(), 1);
+/// ```
+///
+/// [`BoxFuture`]: ./type.BoxFuture.html
+/// [`future::boxed`]: ./future/fn.boxed.html
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+#[macro_export]
+macro_rules! boxed_maybe {
+    (dyn $($rest:tt)+) => {
+        $crate::boxed_maybe!(@dyn [] [] $($rest)+)
+    };
+
+    // Collect trait tokens until top-level comma, tracking angle brackets depth.
+    (@dyn [$($traits:tt)*] [] , $value:expr $(,)?) => {{
+        let boxed: $crate::__private::Box<$crate::dyn_maybe_send!($($traits)*)> =
+            $crate::__private::Box::new($value);
+        boxed
+    }};
+    (@dyn [$($traits:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::boxed_maybe!(@dyn [$($traits)* <] [< $($depth)*] $($rest)*)
+    };
+    (@dyn [$($traits:tt)*] [$d:tt $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::boxed_maybe!(@dyn [$($traits)* >] [$($depth)*] $($rest)*)
+    };
+    (@dyn [$($traits:tt)*] [$d1:tt $d2:tt $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::boxed_maybe!(@dyn [$($traits)* >>] [$($depth)*] $($rest)*)
+    };
+    (@dyn [$($traits:tt)*] $depth:tt $t:tt $($rest:tt)*) => {
+        $crate::boxed_maybe!(@dyn [$($traits)* $t] $depth $($rest)*)
+    };
+
+    ($future:expr $(,)?) => {
+        $crate::future::boxed($future)
+    };
+}
+
+/// Expands to the code when "sync" feature is enabled
+/// and to nothing otherwise.\
+/// With `{ ... } else { ... }` form expands to the second block
+/// when "sync" feature is not enabled.
+///
+/// Works in item, statement and expression positions.
+/// In expression position `else` arm is required.
+/// Braces of the arms are not kept, so each arm must be a single expression there.
+/// With `expr { ... } else { ... }` form arms are expanded as block expressions
+/// and may contain several statements.\
+/// Code that is not chosen is discarded without being type-checked,
+/// so it may use items available only in its configuration.
+///
+/// Unlike `#[cfg(feature = "sync")]` in downstream code,
+/// this follows how `maybe-sync` itself was configured.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{if_sync, MaybeSend};
+/// // Item position.
+/// if_sync! {{
+///     fn spawn(f: impl FnOnce() + MaybeSend + 'static) {
+///         std::thread::spawn(f).join().unwrap();
+///     }
+/// } else {
+///     fn spawn(f: impl FnOnce() + MaybeSend + 'static) {
+///         f();
+///     }
+/// }}
+///
+/// // Statement position.
+/// if_sync! {{
+///     let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+/// } else {
+///     let workers = 1;
+/// }}
+/// assert!(workers >= 1);
+///
+/// // Expression position.
+/// let mode = if_sync!({ "sync" } else { "unsync" });
+/// assert_eq!(mode, if maybe_sync::SYNC { "sync" } else { "unsync" });
+///
+/// // Expression position with several statements in arms.
+/// let threads = if_sync!(expr {
+///     let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+///     threads.min(4)
+/// } else {
+///     1
+/// });
+/// assert!(threads >= 1);
+///
+/// if_sync! {
+///     spawn(move || assert_eq!(mode, "sync"));
+/// }
+/// ```
+#[cfg(feature = "sync")]
+#[macro_export]
+macro_rules! if_sync {
+    (expr { $($when_sync:tt)* } else { $($when_unsync:tt)* }) => {
+        { $($when_sync)* }
+    };
+    ({ $($when_sync:tt)* } else { $($when_unsync:tt)* }) => {
+        $($when_sync)*
+    };
+    ($($when_sync:tt)*) => {
+        $($when_sync)*
+    };
+}
+
+/// Expands to the code when "sync" feature is enabled
+/// and to nothing otherwise.\
+/// With `{ ... } else { ... }` form expands to the second block
+/// when "sync" feature is not enabled.
+///
+/// Works in item, statement and expression positions.
+/// In expression position `else` arm is required.
+/// Braces of the arms are not kept, so each arm must be a single expression there.
+/// With `expr { ... } else { ... }` form arms are expanded as block expressions
+/// and may contain several statements.\
+/// Code that is not chosen is discarded without being type-checked,
+/// so it may use items available only in its configuration.
+///
+/// Unlike `#[cfg(feature = "sync")]` in downstream code,
+/// this follows how `maybe-sync` itself was configured.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{if_sync, MaybeSend};
+/// // Item position.
+/// if_sync! {{
+///     fn spawn(f: impl FnOnce() + MaybeSend + 'static) {
+///         std::thread::spawn(f).join().unwrap();
+///     }
+/// } else {
+///     fn spawn(f: impl FnOnce() + MaybeSend + 'static) {
+///         f();
+///     }
+/// }}
+///
+/// // Statement position.
+/// if_sync! {{
+///     let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+/// } else {
+///     let workers = 1;
+/// }}
+/// assert!(workers >= 1);
+///
+/// // Expression position.
+/// let mode = if_sync!({ "sync" } else { "unsync" });
+/// assert_eq!(mode, if maybe_sync::SYNC { "sync" } else { "unsync" });
+///
+/// // Expression position with several statements in arms.
+/// let threads = if_sync!(expr {
+///     let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+///     threads.min(4)
+/// } else {
+///     1
+/// });
+/// assert!(threads >= 1);
+///
+/// if_sync! {
+///     spawn(move || assert_eq!(mode, "sync"));
+/// }
+/// ```
+#[cfg(not(feature = "sync"))]
+#[macro_export]
+macro_rules! if_sync {
+    (expr { $($when_sync:tt)* } else { $($when_unsync:tt)* }) => {
+        { $($when_unsync)* }
+    };
+    ({ $($when_sync:tt)* } else { $($when_unsync:tt)* }) => {
+        $($when_unsync)*
+    };
+    ($($when_sync:tt)*) => {};
+}
+
+/// Expands to the code when "sync" feature is not enabled
+/// and to nothing otherwise.\
+/// With `{ ... } else { ... }` form expands to the second block
+/// when "sync" feature is enabled.
+///
+/// Mirror of [`if_sync!`] and works in the same positions.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::if_unsync;
+/// if_unsync! {
+///     // Singlethreaded build may keep non-sendable state.
+///     let counter = std::rc::Rc::new(std::cell::Cell::new(0));
+///     counter.set(1);
+///     assert_eq!(counter.get(), 1);
+/// }
+///
+/// let threads = if_unsync!({ 1 } else { 4 });
+/// assert_eq!(threads, if maybe_sync::SYNC { 4 } else { 1 });
+///
+/// let label = if_unsync!(expr {
+///     let label = String::from("local");
+///     label.len()
+/// } else {
+///     0
+/// });
+/// assert_eq!(label, if maybe_sync::SYNC { 0 } else { 5 });
+/// ```
+///
+/// [`if_sync!`]: ./macro.if_sync.html
+#[macro_export]
+macro_rules! if_unsync {
+    (expr { $($when_unsync:tt)* } else { $($when_sync:tt)* }) => {
+        $crate::if_sync! { expr { $($when_sync)* } else { $($when_unsync)* } }
+    };
+    ({ $($when_unsync:tt)* } else { $($when_sync:tt)* }) => {
+        $crate::if_sync! { { $($when_sync)* } else { $($when_unsync)* } }
+    };
+    ($($when_unsync:tt)*) => {
+        $crate::if_sync! { {} else { $($when_unsync)* } }
+    };
+}
+
+/// Keeps enclosed items when "sync" feature is enabled
+/// and drops them otherwise.
+///
+/// Item-level counterpart of [`if_sync!`].
+/// Accepts only items, including impl blocks, modules and `use` declarations,
+/// together with their attributes and doc comments.\
+/// Dropped items are parsed but not type-checked.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{sync_items, unsync_items};
+/// trait Source {
+///     fn name(&self) -> &'static str;
+/// }
+///
+/// sync_items! {
+///     /// Source that loads on thread pool.
+///     #[derive(Debug, Default)]
+///     struct PoolSource;
+///
+///     impl Source for PoolSource {
+///         fn name(&self) -> &'static str {
+///             "pool"
+///         }
+///     }
+///
+///     mod default_source {
+///         pub(crate) use super::PoolSource as DefaultSource;
+///     }
+/// }
+///
+/// unsync_items! {
+///     /// Source that loads on current thread.
+///     #[derive(Debug, Default)]
+///     struct LocalSource(std::rc::Rc<()>);
+///
+///     impl Source for LocalSource {
+///         fn name(&self) -> &'static str {
+///             "local"
+///         }
+///     }
+///
+///     mod default_source {
+///         pub(crate) use super::LocalSource as DefaultSource;
+///     }
+/// }
+///
+/// use default_source::DefaultSource;
+///
+/// fn main() {
+///     let name = DefaultSource::default().name();
+///     assert_eq!(name, if maybe_sync::SYNC { "pool" } else { "local" });
+/// }
+/// ```
+///
+/// [`if_sync!`]: ./macro.if_sync.html
+#[macro_export]
+macro_rules! sync_items {
+    ($($item:item)*) => {
+        $crate::if_sync! { $($item)* }
+    };
+}
+
+/// Keeps enclosed items when "sync" feature is not enabled
+/// and drops them otherwise.
+///
+/// Mirror of [`sync_items!`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::unsync_items;
+/// unsync_items! {
+///     use std::rc::Rc as Handle;
+/// }
+///
+/// maybe_sync::sync_items! {
+///     use std::sync::Arc as Handle;
+/// }
+///
+/// let handle = Handle::new(42);
+/// assert_eq!(*handle, 42);
+/// ```
+///
+/// [`sync_items!`]: ./macro.sync_items.html
+#[macro_export]
+macro_rules! unsync_items {
+    ($($item:item)*) => {
+        $crate::if_unsync! { $($item)* }
+    };
+}
+
+/// Emits impl blocks from `sync` arm when "sync" feature is enabled
+/// and from `unsync` arm otherwise.
+///
+/// Both arms are optional and may contain any number of items,
+/// including generic and `unsafe` impl blocks.\
+/// Impls from the other arm are parsed but not type-checked.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{future::now_or_never, maybe_impl, BoxFuture}, std::{cell::RefCell, collections::VecDeque}};
+/// pub trait Executor {
+///     fn spawn(&self, task: BoxFuture<'static, ()>);
+/// }
+///
+/// #[derive(Default)]
+/// pub struct Runtime {
+///     queue: RefCell<VecDeque<BoxFuture<'static, ()>>>,
+/// }
+///
+/// maybe_impl! {
+///     sync {
+///         impl Executor for Runtime {
+///             fn spawn(&self, task: BoxFuture<'static, ()>) {
+///                 std::thread::spawn(move || now_or_never(task)).join().unwrap();
+///             }
+///         }
+///     }
+///     unsync {
+///         impl Executor for Runtime {
+///             fn spawn(&self, task: BoxFuture<'static, ()>) {
+///                 self.queue.borrow_mut().push_back(task);
+///             }
+///         }
+///     }
+/// }
+///
+/// impl Runtime {
+///     fn run(&self) {
+///         while let Some(task) = self.queue.borrow_mut().pop_front() {
+///             now_or_never(task);
+///         }
+///     }
+/// }
+///
+/// let runtime = Runtime::default();
+/// let counter = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+/// for _ in 0..3 {
+///     let counter = counter.clone();
+///     runtime.spawn(Box::pin(async move {
+///         counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+///     }));
+/// }
+/// runtime.run();
+/// assert_eq!(counter.load(std::sync::atomic::Ordering::Relaxed), 3);
+/// ```
+///
+/// Generic `unsafe impl` needed only in multithreaded build.
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use maybe_sync::{maybe_impl, send_ok};
+/// pub struct Handle<T> {
+///     ptr: *const T,
+/// }
+///
+/// maybe_impl! {
+///     sync {
+///         // Safety: `Handle` gives only shared access to `T`.
+///         unsafe impl<T: Sync> Send for Handle<T> {}
+///     }
+/// }
+///
+/// send_ok::<Handle<u32>>();
+/// ```
+#[macro_export]
+macro_rules! maybe_impl {
+    ($(sync { $($when_sync:item)* })? $(unsync { $($when_unsync:item)* })?) => {
+        $crate::if_sync! { { $($($when_sync)*)? } else { $($($when_unsync)*)? } }
+    };
+}
+
+/// Emits compile error if "sync" feature state of `maybe-sync`
+/// differs from the expected one.
+///
+/// Consider binary crate `app` that depends on libraries `a` and `b`,
+/// both depending on `maybe-sync`.
+/// `a` spawns its futures on thread pool and enables "sync" feature,
+/// `b` forwards its own "sync" feature that nobody enables.
+/// Cargo builds `maybe-sync` once, with "sync" feature, so `b` gets multithreaded types
+/// it did not ask for. Or `a` forgets to enable the feature
+/// and `app` silently gets singlethreaded build.
+///
+/// Top-level crate can state which build it expects
+/// with `sync = true` or `sync = false`.
+/// Feature state is also available as [`SYNC`] constant.
+///
+/// # Example
+///
+#[cfg_attr(feature = "sync", doc = "```")]
+#[cfg_attr(not(feature = "sync"), doc = "```compile_fail")]
+/// maybe_sync::assert_feature_matches!(sync = true);
+/// ```
+///
+#[cfg_attr(feature = "sync", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "sync"), doc = "```")]
+/// maybe_sync::assert_feature_matches!(sync = false);
+/// ```
+///
+/// [`SYNC`]: ./constant.SYNC.html
+#[cfg(feature = "sync")]
+#[macro_export]
+macro_rules! assert_feature_matches {
+    (sync = true $(,)?) => {};
+    (sync = false $(,)?) => {
+        compile_error!(
+            "`maybe-sync` is built with \"sync\" feature, but `sync = false` is expected"
+        );
+    };
+}
+
+/// Emits compile error if "sync" feature state of `maybe-sync`
+/// differs from the expected one.
+///
+/// Consider binary crate `app` that depends on libraries `a` and `b`,
+/// both depending on `maybe-sync`.
+/// `a` spawns its futures on thread pool and enables "sync" feature,
+/// `b` forwards its own "sync" feature that nobody enables.
+/// Cargo builds `maybe-sync` once, with "sync" feature, so `b` gets multithreaded types
+/// it did not ask for. Or `a` forgets to enable the feature
+/// and `app` silently gets singlethreaded build.
+///
+/// Top-level crate can state which build it expects
+/// with `sync = true` or `sync = false`.
+/// Feature state is also available as [`SYNC`] constant.
+///
+/// # Example
+///
+#[cfg_attr(feature = "sync", doc = "```")]
+#[cfg_attr(not(feature = "sync"), doc = "```compile_fail")]
+/// maybe_sync::assert_feature_matches!(sync = true);
+/// ```
+///
+#[cfg_attr(feature = "sync", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "sync"), doc = "```")]
+/// maybe_sync::assert_feature_matches!(sync = false);
+/// ```
+///
+/// [`SYNC`]: ./constant.SYNC.html
+#[cfg(not(feature = "sync"))]
+#[macro_export]
+macro_rules! assert_feature_matches {
+    (sync = true $(,)?) => {
+        compile_error!(
+            "`maybe-sync` is built without \"sync\" feature, but `sync = true` is expected"
+        );
+    };
+    (sync = false $(,)?) => {};
+}
@@ -0,0 +1,132 @@
+use {
+    crate::{MappedMutexGuard, Mutex, MutexGuardMapExt},
+    core::fmt::{self, Debug, Display},
+};
+
+/// Value that is initialized once and can be read or mutated afterwards.
+///
+/// Wraps `Mutex<Option<T>>`, so unlike `OnceCell`
+/// the value can be mutated in place after initialization via [`lock`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{AlreadyInitialized, LockStep};
+/// let config = LockStep::new();
+/// assert_eq!(config.get_cloned(), None::<Vec<u32>>);
+///
+/// config.init(vec![1]).unwrap();
+/// assert_eq!(config.init(vec![2]), Err(AlreadyInitialized));
+///
+/// config.lock().unwrap().push(3);
+/// assert_eq!(config.get_cloned(), Some(vec![1, 3]));
+/// ```
+///
+/// [`lock`]: ./struct.LockStep.html#method.lock
+pub struct LockStep<T> {
+    value: Mutex<Option<T>>,
+}
+
+impl<T> LockStep<T> {
+    /// Creates new uninitialized value.
+    pub fn new() -> Self {
+        LockStep {
+            value: Mutex::new(None),
+        }
+    }
+
+    /// Initializes the value.\
+    /// Returns [`AlreadyInitialized`] leaving current value intact
+    /// if it was initialized before.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{AlreadyInitialized, LockStep};
+    /// let plugin = LockStep::new();
+    ///
+    /// #[cfg(feature = "sync")]
+    /// std::thread::scope(|scope| {
+    ///     let plugin = &plugin;
+    ///     let threads: Vec<_> = (0..4).map(|i| scope.spawn(move || plugin.init(i))).collect();
+    ///     let results = threads.into_iter().map(|t| t.join().unwrap());
+    ///     assert_eq!(results.filter(Result::is_ok).count(), 1);
+    /// });
+    ///
+    /// #[cfg(not(feature = "sync"))]
+    /// assert_eq!(plugin.init(0), Ok(()));
+    ///
+    /// assert_eq!(plugin.init(5), Err(AlreadyInitialized));
+    /// assert!(plugin.get_cloned().unwrap() < 4);
+    /// ```
+    ///
+    /// [`AlreadyInitialized`]: ./struct.AlreadyInitialized.html
+    pub fn init(&self, value: T) -> Result<(), AlreadyInitialized> {
+        let mut slot = self.value.lock();
+        if slot.is_some() {
+            return Err(AlreadyInitialized);
+        }
+        *slot = Some(value);
+        Ok(())
+    }
+
+    /// Returns `true` if the value was initialized.
+    pub fn is_initialized(&self) -> bool {
+        self.value.lock().is_some()
+    }
+
+    /// Returns clone of the value if it was initialized.
+    pub fn get_cloned(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.value.lock().clone()
+    }
+
+    /// Locks the value for reading and mutation.\
+    /// Returns `None` if the value was not initialized.
+    pub fn lock(&self) -> Option<MappedMutexGuard<'_, T>> {
+        self.value.lock().try_map(Option::as_mut).ok()
+    }
+
+    /// Returns mutable reference to the value if it was initialized.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut().as_mut()
+    }
+
+    /// Returns the value if it was initialized.
+    pub fn into_inner(self) -> Option<T> {
+        self.value.into_inner()
+    }
+}
+
+impl<T> Default for LockStep<T> {
+    fn default() -> Self {
+        LockStep::new()
+    }
+}
+
+impl<T> Debug for LockStep<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LockStep")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// Error returned from [`LockStep::init`] when the value was already initialized.
+///
+/// [`LockStep::init`]: ./struct.LockStep.html#method.init
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AlreadyInitialized;
+
+impl Display for AlreadyInitialized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value is already initialized")
+    }
+}
+
+impl core::error::Error for AlreadyInitialized {}
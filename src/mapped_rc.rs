@@ -0,0 +1,121 @@
+use {
+    crate::{MaybeSend, MaybeSync, Rc},
+    core::{fmt, ops::Deref, ptr::NonNull},
+};
+
+trait Erased {}
+
+impl<T> Erased for T where T: ?Sized {}
+
+/// Shared pointer to a part of the value owned by [`Rc`].
+///
+/// Keeps whole allocation alive while dereferencing to the projected part.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{MappedRc, Rc};
+/// struct Scene {
+///     lights: Vec<u32>,
+/// }
+///
+/// let scene = Rc::new(Scene { lights: vec![1, 2, 3] });
+/// let lights = MappedRc::new(scene, |scene| &scene.lights);
+/// let first = MappedRc::map(lights.clone(), |lights| &lights[0]);
+///
+/// drop(lights);
+/// assert_eq!(*first, 1);
+/// ```
+///
+/// [`Rc`]: ./type.Rc.html
+pub struct MappedRc<T: ?Sized> {
+    // Safety: `ptr` points into the value owned by `owner`.
+    // The value is never mutated while `owner` is alive
+    // as this pointer is never exposed for `Rc::get_mut` or similar.
+    ptr: NonNull<T>,
+    owner: Rc<crate::dyn_maybe_send_sync!(Erased)>,
+}
+
+// `MappedRc` behaves like `&T` that keeps `Send + Sync` owner alive.
+#[cfg(feature = "sync")]
+unsafe impl<T> Send for MappedRc<T> where T: Sync + ?Sized {}
+
+#[cfg(feature = "sync")]
+unsafe impl<T> Sync for MappedRc<T> where T: Sync + ?Sized {}
+
+impl<T> MappedRc<T>
+where
+    T: ?Sized,
+{
+    /// Projects value owned by [`Rc`] into one of its parts.
+    ///
+    /// [`Rc`]: ./type.Rc.html
+    pub fn new<O>(owner: Rc<O>, f: impl FnOnce(&O) -> &T) -> Self
+    where
+        O: MaybeSend + MaybeSync + 'static,
+    {
+        let ptr = NonNull::from(f(&owner));
+        MappedRc { ptr, owner }
+    }
+
+    /// Projects already mapped pointer further.
+    pub fn map<U>(this: Self, f: impl FnOnce(&T) -> &U) -> MappedRc<U>
+    where
+        U: ?Sized,
+    {
+        let ptr = NonNull::from(f(&this));
+        MappedRc {
+            ptr,
+            owner: this.owner,
+        }
+    }
+
+    /// Returns `true` if both pointers point to the same part
+    /// of the same allocation.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        core::ptr::eq(this.ptr.as_ptr(), other.ptr.as_ptr())
+            && crate::rc::ptr_eq(&this.owner, &other.owner)
+    }
+}
+
+impl<T> Clone for MappedRc<T>
+where
+    T: ?Sized,
+{
+    fn clone(&self) -> Self {
+        MappedRc {
+            ptr: self.ptr,
+            owner: self.owner.clone(),
+        }
+    }
+}
+
+impl<T> Deref for MappedRc<T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety: `owner` keeps value alive and it is never mutated.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> fmt::Debug for MappedRc<T>
+where
+    T: fmt::Debug + ?Sized,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, fmt)
+    }
+}
+
+impl<T> fmt::Display for MappedRc<T>
+where
+    T: fmt::Display + ?Sized,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, fmt)
+    }
+}
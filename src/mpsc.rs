@@ -0,0 +1,501 @@
+//! Multi-producer single-consumer channels.
+//!
+//! [`Sender`], [`BoundedSender`] and [`Receiver`] are sendable
+//! when "sync" feature is enabled and `T: Send`.\
+//! Blocking [`Receiver::recv`] is available only when "sync" feature is enabled,
+//! as there is no other thread to send a value while current one is blocked
+//! otherwise. Use [`Receiver::try_recv`] in code that compiles in both modes.
+//!
+//! With both "crossbeam" and "sync" features channels are backed by `crossbeam-channel`,
+//! which scales better with many producer threads, and expose exactly the same API.
+//! Without "sync" feature "crossbeam" has no effect.
+//!
+//! # Example
+//!
+//! ```
+//! # use maybe_sync::mpsc::{self, TryRecvError};
+//! let (tx, rx) = mpsc::unbounded();
+//! let tx2 = tx.clone();
+//! tx.send(1).unwrap();
+//! tx2.send(2).unwrap();
+//! assert_eq!(rx.try_recv(), Ok(1));
+//! assert_eq!(rx.try_recv(), Ok(2));
+//! assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+//!
+//! drop((tx, tx2));
+//! assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+//! ```
+//!
+//! [`Sender`]: ./struct.Sender.html
+//! [`BoundedSender`]: ./struct.BoundedSender.html
+//! [`Receiver`]: ./struct.Receiver.html
+//! [`Receiver::recv`]: ./struct.Receiver.html#method.recv
+//! [`Receiver::try_recv`]: ./struct.Receiver.html#method.try_recv
+
+use {
+    crate::MaybeSend,
+    core::fmt::{self, Debug, Display},
+};
+
+#[cfg(not(all(feature = "crossbeam", feature = "sync")))]
+mod chan;
+
+#[cfg(not(all(feature = "crossbeam", feature = "sync")))]
+use self::chan as imp;
+
+#[cfg(all(feature = "crossbeam", feature = "sync"))]
+mod crossbeam;
+
+#[cfg(all(feature = "crossbeam", feature = "sync"))]
+use self::crossbeam as imp;
+
+/// Creates new unbounded channel.
+pub fn unbounded<T>() -> (Sender<T>, Receiver<T>)
+where
+    T: MaybeSend,
+{
+    let (tx, rx) = imp::unbounded();
+    (Sender { inner: tx }, Receiver { inner: rx })
+}
+
+/// Creates new channel that holds at most `cap` values.\
+/// Zero `cap` creates rendezvous channel, same as [`rendezvous`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::mpsc::{self, TrySendError};
+/// let (tx, rx) = mpsc::bounded(2);
+/// tx.try_send(1).unwrap();
+/// tx.try_send(2).unwrap();
+/// assert!(tx.is_full());
+/// assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+///
+/// assert_eq!(rx.try_recv(), Ok(1));
+/// assert_eq!(tx.len(), 1);
+/// tx.try_send(3).unwrap();
+///
+/// drop(rx);
+/// assert_eq!(tx.try_send(4), Err(TrySendError::Disconnected(4)));
+/// ```
+///
+/// [`rendezvous`]: ./fn.rendezvous.html
+pub fn bounded<T>(cap: usize) -> (BoundedSender<T>, Receiver<T>)
+where
+    T: MaybeSend,
+{
+    let (tx, rx) = imp::bounded(cap);
+    (BoundedSender { inner: tx }, Receiver { inner: rx })
+}
+
+/// Creates new zero-capacity channel.\
+/// Each sent value is handed directly to the [`Receiver`].
+///
+/// When "sync" feature is enabled [`BoundedSender::send`] blocks
+/// until [`Receiver`] takes the value,
+/// and [`BoundedSender::try_send`] succeeds only if [`Receiver`]
+/// is already blocked in [`Receiver::recv`].
+///
+/// When "sync" feature is not enabled a thread can't rendezvous with itself.
+/// [`BoundedSender::send`] returns [`TrySendError::WouldDeadlock`] instead of hanging
+/// and [`BoundedSender::try_send`] always returns [`TrySendError::Full`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::mpsc::{self, TrySendError};
+/// let (tx, rx) = mpsc::rendezvous();
+/// assert_eq!(tx.capacity(), 0);
+/// assert!(tx.is_full());
+/// assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+///
+/// #[cfg(feature = "sync")]
+/// {
+///     let worker = std::thread::spawn(move || {
+///         tx.send(1).unwrap();
+///         tx.send(2).unwrap();
+///     });
+///     assert_eq!(rx.recv(), Ok(1));
+///     assert_eq!(rx.recv(), Ok(2));
+///     assert_eq!(rx.recv(), Err(mpsc::RecvError));
+///     worker.join().unwrap();
+/// }
+///
+/// #[cfg(feature = "sync")]
+/// {
+///     let (tx, rx) = mpsc::rendezvous();
+///     let worker = std::thread::spawn(move || rx.recv());
+///
+///     // Succeeds once the worker is blocked in `recv`.
+///     let mut value = 42;
+///     while let Err(TrySendError::Full(v)) = tx.try_send(value) {
+///         value = v;
+///         std::thread::yield_now();
+///     }
+///     assert_eq!(worker.join().unwrap(), Ok(42));
+/// }
+///
+/// #[cfg(feature = "sync")]
+/// {
+///     // Value is returned if receiver is dropped without taking it.
+///     let (tx, rx) = mpsc::rendezvous();
+///     let worker = std::thread::spawn(move || tx.send(1));
+///     drop(rx);
+///     assert_eq!(worker.join().unwrap(), Err(TrySendError::Disconnected(1)));
+/// }
+///
+/// #[cfg(not(feature = "sync"))]
+/// {
+///     assert_eq!(tx.send(1), Err(TrySendError::WouldDeadlock(1)));
+///     assert_eq!(rx.try_recv(), Err(mpsc::TryRecvError::Empty));
+///
+///     drop(rx);
+///     assert_eq!(tx.send(1), Err(TrySendError::Disconnected(1)));
+/// }
+/// ```
+///
+/// [`Receiver`]: ./struct.Receiver.html
+/// [`Receiver::recv`]: ./struct.Receiver.html#method.recv
+/// [`BoundedSender::send`]: ./struct.BoundedSender.html#method.send
+/// [`BoundedSender::try_send`]: ./struct.BoundedSender.html#method.try_send
+/// [`TrySendError::WouldDeadlock`]: ./enum.TrySendError.html#variant.WouldDeadlock
+/// [`TrySendError::Full`]: ./enum.TrySendError.html#variant.Full
+pub fn rendezvous<T>() -> (BoundedSender<T>, Receiver<T>)
+where
+    T: MaybeSend,
+{
+    bounded(0)
+}
+
+/// Sending half of the channel.\
+/// Can be cloned to send values from multiple places.
+pub struct Sender<T> {
+    inner: imp::Sender<T>,
+}
+
+impl<T> Sender<T> {
+    /// Sends value to the [`Receiver`].\
+    /// Returns value back if [`Receiver`] was dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::mpsc::{self, SendError};
+    /// let (tx, rx) = mpsc::unbounded();
+    /// drop(rx);
+    /// assert_eq!(tx.send(42), Err(SendError(42)));
+    /// ```
+    ///
+    /// [`Receiver`]: ./struct.Receiver.html
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.inner.send(value).map_err(SendError)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Sender {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish()
+    }
+}
+
+/// Sending half of the bounded channel.\
+/// Can be cloned to send values from multiple places.
+pub struct BoundedSender<T> {
+    inner: imp::BoundedSender<T>,
+}
+
+impl<T> BoundedSender<T> {
+    /// Sends value to the [`Receiver`].
+    ///
+    /// When "sync" feature is enabled this blocks current thread
+    /// while the channel is full and never returns [`TrySendError::Full`].\
+    /// When "sync" feature is not enabled this returns [`TrySendError::Full`]
+    /// instead of blocking, as no other thread could free the space.
+    /// For [`rendezvous`] channel [`TrySendError::WouldDeadlock`] is returned instead.
+    ///
+    /// Returns [`TrySendError::Disconnected`] if [`Receiver`] was dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::mpsc::{self, TrySendError};
+    /// let (tx, rx) = mpsc::bounded(1);
+    /// tx.send(1).unwrap();
+    ///
+    /// #[cfg(feature = "sync")]
+    /// {
+    ///     let tx = tx.clone();
+    ///     let sender = std::thread::spawn(move || tx.send(2));
+    ///     assert_eq!(rx.recv(), Ok(1));
+    ///     assert_eq!(rx.recv(), Ok(2));
+    ///     assert_eq!(sender.join().unwrap(), Ok(()));
+    /// }
+    ///
+    /// #[cfg(not(feature = "sync"))]
+    /// {
+    ///     assert_eq!(tx.send(2), Err(TrySendError::Full(2)));
+    ///     assert_eq!(rx.try_recv(), Ok(1));
+    /// }
+    /// ```
+    ///
+    /// [`Receiver`]: ./struct.Receiver.html
+    /// [`TrySendError::Full`]: ./enum.TrySendError.html#variant.Full
+    /// [`TrySendError::Disconnected`]: ./enum.TrySendError.html#variant.Disconnected
+    /// [`TrySendError::WouldDeadlock`]: ./enum.TrySendError.html#variant.WouldDeadlock
+    /// [`rendezvous`]: ./fn.rendezvous.html
+    pub fn send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.inner.send(value)
+    }
+
+    /// Attempts to send value to the [`Receiver`] without blocking.
+    ///
+    /// Returns [`TrySendError::Full`] if the channel is full,
+    /// or [`TrySendError::Disconnected`] if [`Receiver`] was dropped.
+    ///
+    /// [`Receiver`]: ./struct.Receiver.html
+    /// [`TrySendError::Full`]: ./enum.TrySendError.html#variant.Full
+    /// [`TrySendError::Disconnected`]: ./enum.TrySendError.html#variant.Disconnected
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.inner.try_send(value)
+    }
+
+    /// Returns number of values in the channel.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if there are no values in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the channel is full.\
+    /// [`rendezvous`] channel is full unless [`Receiver`] is blocked
+    /// in [`Receiver::recv`].
+    /// With "crossbeam" and "sync" features [`rendezvous`] channel is always full.
+    ///
+    /// [`rendezvous`]: ./fn.rendezvous.html
+    /// [`Receiver`]: ./struct.Receiver.html
+    /// [`Receiver::recv`]: ./struct.Receiver.html#method.recv
+    pub fn is_full(&self) -> bool {
+        self.inner.is_full()
+    }
+
+    /// Returns capacity of the channel.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+}
+
+impl<T> Clone for BoundedSender<T> {
+    fn clone(&self) -> Self {
+        BoundedSender {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for BoundedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedSender")
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// Receiving half of the channel.
+pub struct Receiver<T> {
+    inner: imp::Receiver<T>,
+}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive value without blocking.
+    ///
+    /// Returns [`TryRecvError::Empty`] if there are no values in the channel,
+    /// or [`TryRecvError::Disconnected`] if, in addition,
+    /// all [`Sender`]s were dropped.
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    /// [`TryRecvError::Empty`]: ./enum.TryRecvError.html#variant.Empty
+    /// [`TryRecvError::Disconnected`]: ./enum.TryRecvError.html#variant.Disconnected
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.inner.try_recv()
+    }
+
+    /// Receives value, blocking current thread until one is available.\
+    /// Returns [`RecvError`] if the channel is empty and all [`Sender`]s were dropped.
+    ///
+    /// Available only when "sync" feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::mpsc::{self, RecvError};
+    /// #[cfg(feature = "sync")]
+    /// {
+    ///     let (tx, rx) = mpsc::unbounded();
+    ///     std::thread::spawn(move || tx.send(42).unwrap());
+    ///     assert_eq!(rx.recv(), Ok(42));
+    ///     assert_eq!(rx.recv(), Err(RecvError));
+    /// }
+    /// ```
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    /// [`RecvError`]: ./struct.RecvError.html
+    #[cfg(feature = "sync")]
+    #[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "sync")))]
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.inner.recv()
+    }
+
+    /// Returns number of values in the channel.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if there are no values in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the channel is bounded and full.
+    pub fn is_full(&self) -> bool {
+        self.inner.is_full()
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish()
+    }
+}
+
+/// Error returned from [`Sender::send`] when [`Receiver`] was dropped.\
+/// Contains the value that was not sent.
+///
+/// [`Sender::send`]: ./struct.Sender.html#method.send
+/// [`Receiver`]: ./struct.Receiver.html
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish_non_exhaustive()
+    }
+}
+
+impl<T> Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sending on a closed channel")
+    }
+}
+
+impl<T> core::error::Error for SendError<T> {}
+
+/// Error returned from [`BoundedSender::send`] and [`BoundedSender::try_send`].\
+/// Contains the value that was not sent.
+///
+/// [`BoundedSender::send`]: ./struct.BoundedSender.html#method.send
+/// [`BoundedSender::try_send`]: ./struct.BoundedSender.html#method.try_send
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// Channel is full.
+    Full(T),
+
+    /// Sending on [`rendezvous`] channel would block forever,
+    /// as "sync" feature is not enabled.
+    ///
+    /// [`rendezvous`]: ./fn.rendezvous.html
+    WouldDeadlock(T),
+
+    /// [`Receiver`] was dropped.
+    ///
+    /// [`Receiver`]: ./struct.Receiver.html
+    Disconnected(T),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the value that was not sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(value)
+            | TrySendError::WouldDeadlock(value)
+            | TrySendError::Disconnected(value) => value,
+        }
+    }
+}
+
+impl<T> Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Full(..)"),
+            TrySendError::WouldDeadlock(_) => f.write_str("WouldDeadlock(..)"),
+            TrySendError::Disconnected(_) => f.write_str("Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("sending on a full channel"),
+            TrySendError::WouldDeadlock(_) => {
+                f.write_str("sending on a rendezvous channel would deadlock")
+            }
+            TrySendError::Disconnected(_) => f.write_str("sending on a closed channel"),
+        }
+    }
+}
+
+impl<T> core::error::Error for TrySendError<T> {}
+
+/// Error returned from [`Receiver::try_recv`].
+///
+/// [`Receiver::try_recv`]: ./struct.Receiver.html#method.try_recv
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// Channel is empty but [`Sender`]s are still alive.
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    Empty,
+
+    /// Channel is empty and all [`Sender`]s were dropped.
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    Disconnected,
+}
+
+impl Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("receiving on an empty channel"),
+            TryRecvError::Disconnected => f.write_str("receiving on a closed channel"),
+        }
+    }
+}
+
+impl core::error::Error for TryRecvError {}
+
+/// Error returned from [`Receiver::recv`] when the channel is empty
+/// and all [`Sender`]s were dropped.
+///
+/// [`Sender`]: ./struct.Sender.html
+/// [`Receiver::recv`]: ./struct.Receiver.html#method.recv
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecvError;
+
+impl Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("receiving on a closed channel")
+    }
+}
+
+impl core::error::Error for RecvError {}
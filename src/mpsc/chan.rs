@@ -0,0 +1,313 @@
+//! Backend built on crate's own [`Mutex`].\
+//! Works whether "sync" feature is enabled or not.
+//!
+//! [`Mutex`]: ../../type.Mutex.html
+
+use {
+    super::{TryRecvError, TrySendError},
+    crate::{Mutex, MutexGuard, Rc},
+    alloc::collections::VecDeque,
+};
+
+#[cfg(feature = "sync")]
+use super::RecvError;
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receiver: bool,
+    // Receiver is blocked in `recv`.
+    parked: bool,
+    // Number of values received, used by rendezvous senders
+    // to detect that their value was taken.
+    popped: usize,
+}
+
+struct Chan<T> {
+    state: Mutex<State<T>>,
+    cap: Option<usize>,
+    #[cfg(feature = "sync")]
+    available: parking_lot::Condvar,
+    #[cfg(feature = "sync")]
+    space: parking_lot::Condvar,
+}
+
+impl<T> Chan<T> {
+    fn new(cap: Option<usize>) -> Rc<Self> {
+        Rc::new(Chan {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                senders: 1,
+                receiver: true,
+                parked: false,
+                popped: 0,
+            }),
+            cap,
+            #[cfg(feature = "sync")]
+            available: parking_lot::Condvar::new(),
+            #[cfg(feature = "sync")]
+            space: parking_lot::Condvar::new(),
+        })
+    }
+
+    fn notify_available(&self) {
+        #[cfg(feature = "sync")]
+        self.available.notify_one();
+    }
+
+    fn notify_space(&self) {
+        #[cfg(feature = "sync")]
+        self.space.notify_all();
+    }
+
+    fn is_full(&self, state: &State<T>) -> bool {
+        match self.cap {
+            None => false,
+            Some(0) => !state.parked || !state.queue.is_empty(),
+            Some(cap) => state.queue.len() >= cap,
+        }
+    }
+
+    fn push(&self, mut state: MutexGuard<'_, State<T>>, value: T) {
+        state.queue.push_back(value);
+        drop(state);
+        self.notify_available();
+    }
+
+    fn pop(&self, state: &mut State<T>) -> Option<T> {
+        let value = state.queue.pop_front()?;
+        state.popped = state.popped.wrapping_add(1);
+        if self.cap.is_some() {
+            self.notify_space();
+        }
+        Some(value)
+    }
+
+    fn add_sender(&self) {
+        self.state.lock().senders += 1;
+    }
+
+    fn remove_sender(&self) {
+        let mut state = self.state.lock();
+        state.senders -= 1;
+        let last = state.senders == 0;
+        drop(state);
+
+        // Wake blocked receiver to observe disconnection.
+        if last {
+            self.notify_available();
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.state.lock().queue.len()
+    }
+}
+
+pub(super) fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+    let chan = Chan::new(None);
+    (Sender { chan: chan.clone() }, Receiver { chan })
+}
+
+pub(super) fn bounded<T>(cap: usize) -> (BoundedSender<T>, Receiver<T>) {
+    let chan = Chan::new(Some(cap));
+    (BoundedSender { chan: chan.clone() }, Receiver { chan })
+}
+
+pub(super) struct Sender<T> {
+    chan: Rc<Chan<T>>,
+}
+
+impl<T> Sender<T> {
+    pub(super) fn send(&self, value: T) -> Result<(), T> {
+        let state = self.chan.state.lock();
+        if !state.receiver {
+            return Err(value);
+        }
+        self.chan.push(state, value);
+        Ok(())
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.chan.add_sender();
+        Sender {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.chan.remove_sender();
+    }
+}
+
+pub(super) struct BoundedSender<T> {
+    chan: Rc<Chan<T>>,
+}
+
+impl<T> BoundedSender<T> {
+    #[cfg(feature = "sync")]
+    pub(super) fn send(&self, value: T) -> Result<(), TrySendError<T>> {
+        if self.chan.cap == Some(0) {
+            return self.send_rendezvous(value);
+        }
+
+        let mut state = self.chan.state.lock();
+        loop {
+            if !state.receiver {
+                return Err(TrySendError::Disconnected(value));
+            }
+            if !self.chan.is_full(&state) {
+                self.chan.push(state, value);
+                return Ok(());
+            }
+            self.chan.space.wait(&mut state);
+        }
+    }
+
+    #[cfg(not(feature = "sync"))]
+    pub(super) fn send(&self, value: T) -> Result<(), TrySendError<T>> {
+        match self.try_send(value) {
+            Err(TrySendError::Full(value)) if self.chan.cap == Some(0) => {
+                Err(TrySendError::WouldDeadlock(value))
+            }
+            result => result,
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    fn send_rendezvous(&self, value: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.chan.state.lock();
+
+        // Wait for other senders' values to be taken.
+        loop {
+            if !state.receiver {
+                return Err(TrySendError::Disconnected(value));
+            }
+            if state.queue.is_empty() {
+                break;
+            }
+            self.chan.space.wait(&mut state);
+        }
+
+        let ticket = state.popped;
+        state.queue.push_back(value);
+        self.chan.notify_available();
+
+        // Wait for the value to be taken.
+        // Queue holds only this value until then.
+        loop {
+            if state.popped != ticket {
+                return Ok(());
+            }
+            if !state.receiver {
+                let value = state.queue.pop_back().unwrap();
+                return Err(TrySendError::Disconnected(value));
+            }
+            self.chan.space.wait(&mut state);
+        }
+    }
+
+    pub(super) fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let state = self.chan.state.lock();
+        if !state.receiver {
+            Err(TrySendError::Disconnected(value))
+        } else if self.chan.is_full(&state) {
+            Err(TrySendError::Full(value))
+        } else {
+            self.chan.push(state, value);
+            Ok(())
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.chan.len()
+    }
+
+    pub(super) fn is_full(&self) -> bool {
+        self.chan.is_full(&self.chan.state.lock())
+    }
+
+    pub(super) fn capacity(&self) -> usize {
+        self.chan.cap.unwrap()
+    }
+}
+
+impl<T> Clone for BoundedSender<T> {
+    fn clone(&self) -> Self {
+        self.chan.add_sender();
+        BoundedSender {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for BoundedSender<T> {
+    fn drop(&mut self) {
+        self.chan.remove_sender();
+    }
+}
+
+pub(super) struct Receiver<T> {
+    chan: Rc<Chan<T>>,
+}
+
+impl<T> Receiver<T> {
+    pub(super) fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.chan.state.lock();
+        match self.chan.pop(&mut state) {
+            Some(value) => Ok(value),
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    pub(super) fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.chan.state.lock();
+        loop {
+            if let Some(value) = self.chan.pop(&mut state) {
+                return Ok(value);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+            state.parked = true;
+            self.chan.available.wait(&mut state);
+            state.parked = false;
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.chan.len()
+    }
+
+    pub(super) fn is_full(&self) -> bool {
+        self.chan.is_full(&self.chan.state.lock())
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let queue = {
+            let mut state = self.chan.state.lock();
+            state.receiver = false;
+
+            // Rendezvous senders take their values back.
+            if self.chan.cap == Some(0) {
+                VecDeque::new()
+            } else {
+                core::mem::take(&mut state.queue)
+            }
+        };
+
+        // Wake blocked senders to observe disconnection.
+        self.chan.notify_space();
+
+        // Drop queued values outside of the lock.
+        drop(queue);
+    }
+}
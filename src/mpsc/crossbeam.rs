@@ -0,0 +1,99 @@
+//! Backend built on `crossbeam-channel`.\
+//! Used when both "crossbeam" and "sync" features are enabled.
+
+use {
+    super::{RecvError, TryRecvError, TrySendError},
+    crossbeam_channel as cb,
+};
+
+pub(super) fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = cb::unbounded();
+    (Sender { tx }, Receiver { rx })
+}
+
+pub(super) fn bounded<T>(cap: usize) -> (BoundedSender<T>, Receiver<T>) {
+    let (tx, rx) = cb::bounded(cap);
+    (BoundedSender { tx }, Receiver { rx })
+}
+
+pub(super) struct Sender<T> {
+    tx: cb::Sender<T>,
+}
+
+impl<T> Sender<T> {
+    pub(super) fn send(&self, value: T) -> Result<(), T> {
+        self.tx.send(value).map_err(|cb::SendError(value)| value)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Sender {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+pub(super) struct BoundedSender<T> {
+    tx: cb::Sender<T>,
+}
+
+impl<T> BoundedSender<T> {
+    pub(super) fn send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.tx
+            .send(value)
+            .map_err(|cb::SendError(value)| TrySendError::Disconnected(value))
+    }
+
+    pub(super) fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.tx.try_send(value).map_err(|err| match err {
+            cb::TrySendError::Full(value) => TrySendError::Full(value),
+            cb::TrySendError::Disconnected(value) => TrySendError::Disconnected(value),
+        })
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.tx.len()
+    }
+
+    pub(super) fn is_full(&self) -> bool {
+        self.tx.is_full()
+    }
+
+    pub(super) fn capacity(&self) -> usize {
+        self.tx.capacity().unwrap()
+    }
+}
+
+impl<T> Clone for BoundedSender<T> {
+    fn clone(&self) -> Self {
+        BoundedSender {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+pub(super) struct Receiver<T> {
+    rx: cb::Receiver<T>,
+}
+
+impl<T> Receiver<T> {
+    pub(super) fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.rx.try_recv().map_err(|err| match err {
+            cb::TryRecvError::Empty => TryRecvError::Empty,
+            cb::TryRecvError::Disconnected => TryRecvError::Disconnected,
+        })
+    }
+
+    pub(super) fn recv(&self) -> Result<T, RecvError> {
+        self.rx.recv().map_err(|cb::RecvError| RecvError)
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.rx.len()
+    }
+
+    pub(super) fn is_full(&self) -> bool {
+        self.rx.is_full()
+    }
+}
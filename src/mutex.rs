@@ -0,0 +1,786 @@
+use {
+    crate::{MappedMutexGuard, Mutex, MutexGuard},
+    core::{
+        convert::Infallible,
+        fmt::{self, Display},
+    },
+};
+
+#[cfg(feature = "alloc")]
+use {
+    crate::Rc,
+    core::{
+        fmt::Debug,
+        ops::{Deref, DerefMut},
+        ptr::NonNull,
+    },
+};
+
+#[cfg(feature = "std")]
+use {
+    core::{
+        borrow::Borrow,
+        hash::{BuildHasher, Hash},
+    },
+    std::collections::HashMap,
+};
+
+/// Extension methods for [`Mutex`] that work the same way
+/// whether "sync" feature is enabled or not.
+///
+/// [`Mutex`]: ./type.Mutex.html
+pub trait MutexExt<T: ?Sized> {
+    /// Acquires the lock and calls provided closure with mutable reference
+    /// to the locked value.\
+    /// The lock is released before this function returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt};
+    /// let mutex = Mutex::new(1);
+    /// assert_eq!(mutex.with(|value| { *value += 1; *value }), 2);
+    /// ```
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R;
+
+    /// Attempts to acquire the lock without blocking and calls provided closure
+    /// with mutable reference to the locked value.\
+    /// Returns `None` if the lock could not be acquired at this time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt};
+    /// let mutex = Mutex::new(1);
+    /// assert_eq!(mutex.try_with(|value| *value), Some(1));
+    ///
+    /// let guard = mutex.lock();
+    /// assert_eq!(mutex.try_with(|value| *value), None);
+    /// drop(guard);
+    /// ```
+    fn try_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R>;
+
+    /// Attempts to acquire the lock without blocking.\
+    /// Returns [`WouldBlock`] error if the lock could not be acquired at this time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt, WouldBlock};
+    /// fn increment(mutex: &Mutex<u32>) -> Result<u32, WouldBlock> {
+    ///     let mut guard = mutex.try_lock_err()?;
+    ///     *guard += 1;
+    ///     Ok(*guard)
+    /// }
+    ///
+    /// let mutex = Mutex::new(1);
+    /// assert_eq!(increment(&mutex), Ok(2));
+    ///
+    /// let guard = mutex.lock();
+    /// assert_eq!(increment(&mutex), Err(WouldBlock));
+    /// drop(guard);
+    /// ```
+    ///
+    /// [`WouldBlock`]: ./struct.WouldBlock.html
+    fn try_lock_err(&self) -> Result<MutexGuard<'_, T>, WouldBlock>;
+
+    /// Acquires the lock and applies fallible update to the locked value.\
+    /// Error returned by the closure is returned as is.
+    ///
+    /// No rollback is performed.
+    /// If the closure mutates the value before returning an error,
+    /// restoring previous state is caller's responsibility.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt};
+    /// let config = Mutex::new(8080u16);
+    ///
+    /// let parse = |s: &str| config.try_update(|port| {
+    ///     *port = s.parse().map_err(|_| "invalid port")?;
+    ///     Ok(())
+    /// });
+    ///
+    /// assert_eq!(parse("8000"), Ok(()));
+    /// assert_eq!(parse("port"), Err("invalid port"));
+    /// assert_eq!(*config.lock(), 8000);
+    /// ```
+    fn try_update<E>(&self, f: impl FnOnce(&mut T) -> Result<(), E>) -> Result<(), E>;
+
+    /// Acquires the lock and replaces the value with the one computed
+    /// from the old value, returning the old value.\
+    /// The lock is held while the closure runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt};
+    /// let history = Mutex::new(vec![1]);
+    /// history.replace_with(|v| [v.as_slice(), &[2]].concat());
+    /// history.replace_with(|v| v.iter().map(|x| x * 10).collect());
+    /// let old = history.replace_with(|v| v.iter().rev().copied().collect());
+    ///
+    /// assert_eq!(old, [10, 20]);
+    /// assert_eq!(history.into_inner(), [20, 10]);
+    /// ```
+    fn replace_with(&self, f: impl FnOnce(&T) -> T) -> T
+    where
+        T: Sized;
+
+    /// Alias for `lock` familiar to `RefCell` users.\
+    /// Prefer `lock` in code that may be compiled with "sync" feature,
+    /// as it states intent more clearly.
+    ///
+    /// There is no `borrow` counterpart as [`Mutex`] has no shared guard.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt};
+    /// let mutex = Mutex::new(1);
+    /// *mutex.borrow_mut() += 1;
+    /// assert_eq!(*mutex.lock(), 2);
+    /// ```
+    ///
+    /// [`Mutex`]: ./type.Mutex.html
+    fn borrow_mut(&self) -> MutexGuard<'_, T>;
+
+    /// Calls provided closure with mutable reference to the value
+    /// without locking.\
+    /// Since this call borrows the `Mutex` mutably,
+    /// no actual locking needs to take place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt};
+    /// let mut mutex = Mutex::new(Vec::new());
+    /// let len = mutex.map_mut(|v| {
+    ///     v.extend_from_slice(&[1, 2, 3]);
+    ///     v.len()
+    /// });
+    /// assert_eq!(len, 3);
+    /// assert_eq!(*mutex.lock(), [1, 2, 3]);
+    /// ```
+    fn map_mut<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R;
+
+    /// Acquires the lock returning `Result` like `std::sync::Mutex::lock` does.\
+    /// [`Mutex`] is never poisoned, so this never fails.
+    ///
+    /// Eases porting from `std::sync::Mutex` where `.lock().unwrap()`
+    /// is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt};
+    /// let mutex = Mutex::new(1);
+    /// *mutex.lock_checked().unwrap() += 1;
+    /// assert_eq!(*mutex.lock(), 2);
+    /// ```
+    ///
+    /// [`Mutex`]: ./type.Mutex.html
+    fn lock_checked(&self) -> Result<MutexGuard<'_, T>, Infallible>;
+
+    /// Always returns `false` as [`Mutex`] is never poisoned.
+    ///
+    /// [`Mutex`]: ./type.Mutex.html
+    fn is_poisoned(&self) -> bool;
+
+    /// Does nothing as [`Mutex`] is never poisoned.
+    ///
+    /// [`Mutex`]: ./type.Mutex.html
+    fn clear_poison(&self);
+
+    /// Acquires the lock through [`Rc`] and returns guard that keeps
+    /// the [`Rc`] alive.\
+    /// The guard is `'static` when `T: 'static`,
+    /// so it can be stored inside `'static` future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexExt, Rc};
+    /// let mutex = Rc::new(Mutex::new(1));
+    /// let mut guard = mutex.clone().lock_owned();
+    /// drop(mutex);
+    ///
+    /// *guard += 1;
+    /// assert_eq!(*guard, 2);
+    /// ```
+    ///
+    /// [`Rc`]: ./type.Rc.html
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+    fn lock_owned(self: Rc<Self>) -> OwnedMutexGuard<T>;
+}
+
+impl<T> MutexExt<T> for Mutex<T>
+where
+    T: ?Sized,
+{
+    #[track_caller]
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut *self.lock())
+    }
+
+    fn try_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let mut guard = self.try_lock()?;
+        Some(f(&mut *guard))
+    }
+
+    fn try_lock_err(&self) -> Result<MutexGuard<'_, T>, WouldBlock> {
+        self.try_lock().ok_or(WouldBlock)
+    }
+
+    #[track_caller]
+    fn try_update<E>(&self, f: impl FnOnce(&mut T) -> Result<(), E>) -> Result<(), E> {
+        f(&mut *self.lock())
+    }
+
+    #[track_caller]
+    fn replace_with(&self, f: impl FnOnce(&T) -> T) -> T
+    where
+        T: Sized,
+    {
+        let mut guard = self.lock();
+        let new = f(&guard);
+        core::mem::replace(&mut *guard, new)
+    }
+
+    #[track_caller]
+    fn borrow_mut(&self) -> MutexGuard<'_, T> {
+        self.lock()
+    }
+
+    fn map_mut<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(self.get_mut())
+    }
+
+    #[track_caller]
+    fn lock_checked(&self) -> Result<MutexGuard<'_, T>, Infallible> {
+        Ok(self.lock())
+    }
+
+    fn is_poisoned(&self) -> bool {
+        false
+    }
+
+    fn clear_poison(&self) {}
+
+    #[cfg(all(feature = "alloc", feature = "sync"))]
+    fn lock_owned(self: Rc<Self>) -> OwnedMutexGuard<T> {
+        let mut guard = self.lock();
+        let value = NonNull::from(&mut *guard);
+
+        // Unlocked in `OwnedMutexGuard::drop`.
+        core::mem::forget(guard);
+
+        OwnedMutexGuard { value, mutex: self }
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "sync")))]
+    #[track_caller]
+    fn lock_owned(self: Rc<Self>) -> OwnedMutexGuard<T> {
+        let mut value = None;
+        let borrow = core::cell::RefMut::map(self.lock(), |v| {
+            value = Some(NonNull::from(v));
+            &mut []
+        });
+
+        // Safety: `borrow` is dropped before `mutex` keeping it alive.
+        let borrow = unsafe {
+            core::mem::transmute::<
+                core::cell::RefMut<'_, [(); 0]>,
+                core::cell::RefMut<'static, [(); 0]>,
+            >(borrow)
+        };
+
+        OwnedMutexGuard {
+            value: value.unwrap(),
+            _borrow: borrow,
+            mutex: self,
+        }
+    }
+}
+
+/// RAII guard returned by [`MutexExt::lock_owned`].\
+/// Holds [`Rc`] to the [`Mutex`] instead of borrowing it.
+/// The lock is released when the guard is dropped.
+///
+/// Sendable when "sync" feature is enabled and `T: Send`,
+/// unlike [`MutexGuard`].
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{future::now_or_never, BoxFuture, Mutex, MutexExt, Rc}, std::future::ready};
+/// let mutex = Rc::new(Mutex::new(Vec::new()));
+/// let guard = mutex.clone().lock_owned();
+///
+/// let fut: BoxFuture<'static, usize> = Box::pin(async move {
+///     let mut guard = guard;
+///     guard.push(ready(1).await);
+///     guard.len()
+/// });
+///
+/// assert!(mutex.try_lock().is_none());
+/// assert_eq!(now_or_never(fut), Some(1));
+/// assert_eq!(*mutex.lock(), [1]);
+/// ```
+///
+/// [`MutexExt::lock_owned`]: ./trait.MutexExt.html#tymethod.lock_owned
+/// [`Rc`]: ./type.Rc.html
+/// [`Mutex`]: ./type.Mutex.html
+/// [`MutexGuard`]: ./type.MutexGuard.html
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "alloc")))]
+pub struct OwnedMutexGuard<T: ?Sized> {
+    // Safety: `value` points into locked `mutex`.
+    value: NonNull<T>,
+    // Holds the lock and must be dropped before `mutex`.
+    #[cfg(not(feature = "sync"))]
+    _borrow: core::cell::RefMut<'static, [(); 0]>,
+    mutex: Rc<Mutex<T>>,
+}
+
+// `OwnedMutexGuard` behaves like `&mut T` and `parking_lot` mutex
+// may be unlocked from any thread.
+#[cfg(all(feature = "alloc", feature = "sync"))]
+unsafe impl<T> Send for OwnedMutexGuard<T> where T: Send + ?Sized {}
+
+#[cfg(all(feature = "alloc", feature = "sync"))]
+unsafe impl<T> Sync for OwnedMutexGuard<T> where T: Sync + ?Sized {}
+
+#[cfg(feature = "alloc")]
+impl<T> OwnedMutexGuard<T>
+where
+    T: ?Sized,
+{
+    /// Returns [`Rc`] to the locked [`Mutex`].
+    ///
+    /// [`Rc`]: ./type.Rc.html
+    /// [`Mutex`]: ./type.Mutex.html
+    pub fn mutex(this: &Self) -> &Rc<Mutex<T>> {
+        &this.mutex
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Deref for OwnedMutexGuard<T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety: the lock is held while the guard is alive.
+        unsafe { self.value.as_ref() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DerefMut for OwnedMutexGuard<T>
+where
+    T: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: the lock is held while the guard is alive.
+        unsafe { self.value.as_mut() }
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "sync"))]
+impl<T> Drop for OwnedMutexGuard<T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        // Safety: the lock was acquired in `lock_owned` and its guard forgotten.
+        unsafe { self.mutex.force_unlock() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Debug for OwnedMutexGuard<T>
+where
+    T: Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// Error returned from [`MutexExt::try_lock_err`]
+/// when the lock could not be acquired without blocking.
+///
+/// [`MutexExt::try_lock_err`]: ./trait.MutexExt.html#tymethod.try_lock_err
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WouldBlock;
+
+impl Display for WouldBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("lock is held and acquiring it would block")
+    }
+}
+
+impl core::error::Error for WouldBlock {}
+
+fn addr<T: ?Sized>(mutex: &Mutex<T>) -> usize {
+    mutex as *const Mutex<T> as *const u8 as usize
+}
+
+/// Locks two mutexes and returns guards in argument order.\
+/// Mutexes are always acquired in order of their addresses,
+/// so two threads calling `lock2(a, b)` and `lock2(b, a)`
+/// can't deadlock each other when "sync" feature is enabled.\
+/// Without "sync" feature both are simply locked.
+///
+/// # Panics
+///
+/// Panics if both arguments refer to the same mutex.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{lock2, Mutex};
+/// let a = Mutex::new(1);
+/// let b = Mutex::new(2);
+/// let (mut a_guard, mut b_guard) = lock2(&a, &b);
+/// core::mem::swap(&mut *a_guard, &mut *b_guard);
+/// drop((a_guard, b_guard));
+/// assert_eq!((*a.lock(), *b.lock()), (2, 1));
+///
+/// #[cfg(feature = "sync")]
+/// {
+///     use std::{sync::Arc, thread};
+///     let a = Arc::new(Mutex::new(0u32));
+///     let b = Arc::new(Mutex::new(0u32));
+///     let threads: Vec<_> = (0..4)
+///         .map(|i| {
+///             let (a, b) = (a.clone(), b.clone());
+///             thread::spawn(move || {
+///                 for _ in 0..1000 {
+///                     // Half of the threads pass mutexes in reverse order.
+///                     let (mut x, mut y) = if i % 2 == 0 {
+///                         lock2(&*a, &*b)
+///                     } else {
+///                         let (y, x) = lock2(&*b, &*a);
+///                         (x, y)
+///                     };
+///                     *x += 1;
+///                     *y += 1;
+///                 }
+///             })
+///         })
+///         .collect();
+///     for t in threads {
+///         t.join().unwrap();
+///     }
+///     assert_eq!((*a.lock(), *b.lock()), (4000, 4000));
+/// }
+/// ```
+#[track_caller]
+pub fn lock2<'a, A, B>(a: &'a Mutex<A>, b: &'a Mutex<B>) -> (MutexGuard<'a, A>, MutexGuard<'a, B>) {
+    assert_ne!(addr(a), addr(b), "`lock2` called with the same mutex twice");
+    if addr(a) < addr(b) {
+        let a = a.lock();
+        (a, b.lock())
+    } else {
+        let b = b.lock();
+        (a.lock(), b)
+    }
+}
+
+/// Locks three mutexes and returns guards in argument order.\
+/// Mutexes are acquired in order of their addresses, same as in [`lock2`].
+///
+/// # Panics
+///
+/// Panics if any two arguments refer to the same mutex.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{lock3, Mutex};
+/// let a = Mutex::new(1);
+/// let b = Mutex::new("two");
+/// let c = Mutex::new(3.0);
+/// let (c_guard, a_guard, b_guard) = lock3(&c, &a, &b);
+/// assert_eq!((*a_guard, *b_guard, *c_guard), (1, "two", 3.0));
+/// ```
+///
+/// [`lock2`]: ./fn.lock2.html
+#[track_caller]
+pub fn lock3<'a, A, B, C>(
+    a: &'a Mutex<A>,
+    b: &'a Mutex<B>,
+    c: &'a Mutex<C>,
+) -> (MutexGuard<'a, A>, MutexGuard<'a, B>, MutexGuard<'a, C>) {
+    let mut order = [(addr(a), 0), (addr(b), 1), (addr(c), 2)];
+    order.sort_unstable();
+    assert!(
+        order[0].0 != order[1].0 && order[1].0 != order[2].0,
+        "`lock3` called with the same mutex twice"
+    );
+
+    let (mut ga, mut gb, mut gc) = (None, None, None);
+    for (_, index) in order {
+        match index {
+            0 => ga = Some(a.lock()),
+            1 => gb = Some(b.lock()),
+            _ => gc = Some(c.lock()),
+        }
+    }
+    (ga.unwrap(), gb.unwrap(), gc.unwrap())
+}
+
+/// Extension methods for [`Mutex`] holding lazily initialized value.
+///
+/// [`Mutex`]: ./type.Mutex.html
+pub trait MutexOptionExt<T> {
+    /// Acquires the lock, initializes the value if it is `None`
+    /// and returns clone of the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexOptionExt};
+    /// let mutex = Mutex::new(None);
+    /// let mut calls = 0;
+    /// assert_eq!(mutex.get_or_insert_with(|| { calls += 1; 42 }), 42);
+    /// assert_eq!(mutex.get_or_insert_with(|| { calls += 1; 13 }), 42);
+    /// assert_eq!(calls, 1);
+    /// ```
+    fn get_or_insert_with(&self, init: impl FnOnce() -> T) -> T
+    where
+        T: Clone;
+
+    /// Acquires the lock, initializes the value if it is `None`
+    /// and calls provided closure with mutable reference to the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexOptionExt};
+    /// let mutex = Mutex::new(None);
+    /// let mut calls = 0;
+    /// for _ in 0..3 {
+    ///     mutex.with_or_init(|| { calls += 1; Vec::new() }, |v| v.push(1));
+    /// }
+    /// assert_eq!(calls, 1);
+    /// assert_eq!(mutex.lock().as_deref(), Some(&[1, 1, 1][..]));
+    /// ```
+    fn with_or_init<R>(&self, init: impl FnOnce() -> T, f: impl FnOnce(&mut T) -> R) -> R;
+}
+
+impl<T> MutexOptionExt<T> for Mutex<Option<T>> {
+    #[track_caller]
+    fn get_or_insert_with(&self, init: impl FnOnce() -> T) -> T
+    where
+        T: Clone,
+    {
+        self.lock().get_or_insert_with(init).clone()
+    }
+
+    #[track_caller]
+    fn with_or_init<R>(&self, init: impl FnOnce() -> T, f: impl FnOnce(&mut T) -> R) -> R {
+        f(self.lock().get_or_insert_with(init))
+    }
+}
+
+/// Extension methods for [`Mutex`] holding `HashMap`.\
+/// Each method acquires the lock for the duration of the call.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{Mutex, MutexMapExt}, std::collections::HashMap};
+/// let map = Mutex::new(HashMap::new());
+/// assert_eq!(map.insert("a", 1), None);
+/// assert_eq!(map.get_cloned("a"), Some(1));
+/// assert_eq!(map.entry_or_insert_with("a", || 2), 1);
+/// assert_eq!(map.entry_or_insert_with("b", || 2), 2);
+/// assert_eq!(map.remove("a"), Some(1));
+/// assert_eq!(map.get_cloned("a"), None);
+/// ```
+///
+/// [`Mutex`]: ./type.Mutex.html
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "std")))]
+pub trait MutexMapExt<K, V> {
+    /// Inserts key-value pair into the map.\
+    /// Returns previous value associated with the key.
+    fn insert(&self, key: K, value: V) -> Option<V>;
+
+    /// Returns clone of the value associated with the key.
+    fn get_cloned<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        V: Clone;
+
+    /// Removes the key from the map.\
+    /// Returns value associated with the key.
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized;
+
+    /// Returns clone of the value associated with the key.\
+    /// Inserts value returned by `init` if there is none.
+    fn entry_or_insert_with(&self, key: K, init: impl FnOnce() -> V) -> V
+    where
+        V: Clone;
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> MutexMapExt<K, V> for Mutex<HashMap<K, V, S>>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    #[track_caller]
+    fn insert(&self, key: K, value: V) -> Option<V> {
+        self.lock().insert(key, value)
+    }
+
+    #[track_caller]
+    fn get_cloned<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        V: Clone,
+    {
+        self.lock().get(key).cloned()
+    }
+
+    #[track_caller]
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.lock().remove(key)
+    }
+
+    #[track_caller]
+    fn entry_or_insert_with(&self, key: K, init: impl FnOnce() -> V) -> V
+    where
+        V: Clone,
+    {
+        self.lock().entry(key).or_insert_with(init).clone()
+    }
+}
+
+/// Extension methods for [`MutexGuard`] that work the same way
+/// whether "sync" feature is enabled or not.
+///
+/// [`MutexGuard`]: ./type.MutexGuard.html
+pub trait MutexGuardExt {
+    /// Unlocks the mutex using a fair unlock protocol.
+    ///
+    /// Forwards to `parking_lot::MutexGuard::unlock_fair`
+    /// when "sync" feature is enabled, handing the lock to the next waiting thread.\
+    /// Simply drops the guard when "sync" feature is not enabled,
+    /// as there are no other threads waiting for the lock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexGuardExt};
+    /// let mutex = Mutex::new(1);
+    /// let mut guard = mutex.lock();
+    /// *guard += 1;
+    /// guard.unlock_fair();
+    /// assert_eq!(*mutex.lock(), 2);
+    /// ```
+    fn unlock_fair(self);
+}
+
+impl<T> MutexGuardExt for MutexGuard<'_, T>
+where
+    T: ?Sized,
+{
+    #[cfg(feature = "sync")]
+    fn unlock_fair(self) {
+        MutexGuard::unlock_fair(self)
+    }
+
+    #[cfg(not(feature = "sync"))]
+    fn unlock_fair(self) {
+        drop(self)
+    }
+}
+
+/// Fallible projection of [`MutexGuard`] and [`MappedMutexGuard`]
+/// that works the same way whether "sync" feature is enabled or not.
+///
+/// [`MutexGuard`]: ./type.MutexGuard.html
+/// [`MappedMutexGuard`]: ./type.MappedMutexGuard.html
+pub trait MutexGuardMapExt<'a, T: ?Sized>: Sized {
+    /// Makes a new guard for a component of the locked data.\
+    /// Returns the original guard if the closure returns `None`,
+    /// mirroring `RefMut::filter_map`.
+    ///
+    /// Forwards to `parking_lot::MutexGuard::try_map` when "sync" feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{Mutex, MutexGuardMapExt};
+    /// let mutex = Mutex::new(vec![1, 2, 3]);
+    ///
+    /// let guard = mutex.lock();
+    /// let guard = match guard.try_map(|v| v.get_mut(5)) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(guard) => guard,
+    /// };
+    /// assert_eq!(*guard, [1, 2, 3]);
+    ///
+    /// let mut last = guard.try_map(|v| v.last_mut()).unwrap();
+    /// *last = 42;
+    /// drop(last);
+    /// assert_eq!(*mutex.lock(), [1, 2, 42]);
+    /// ```
+    fn try_map<U: ?Sized>(
+        self,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Result<MappedMutexGuard<'a, U>, Self>;
+}
+
+impl<'a, T> MutexGuardMapExt<'a, T> for MutexGuard<'a, T>
+where
+    T: ?Sized,
+{
+    #[cfg(feature = "sync")]
+    fn try_map<U: ?Sized>(
+        self,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Result<MappedMutexGuard<'a, U>, Self> {
+        MutexGuard::try_map(self, f)
+    }
+
+    #[cfg(not(feature = "sync"))]
+    fn try_map<U: ?Sized>(
+        self,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Result<MappedMutexGuard<'a, U>, Self> {
+        core::cell::RefMut::filter_map(self, f)
+    }
+}
+
+// Without "sync" feature `MappedMutexGuard` is the same type as `MutexGuard`.
+#[cfg(feature = "sync")]
+impl<'a, T> MutexGuardMapExt<'a, T> for MappedMutexGuard<'a, T>
+where
+    T: ?Sized,
+{
+    fn try_map<U: ?Sized>(
+        self,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Result<MappedMutexGuard<'a, U>, Self> {
+        MappedMutexGuard::try_map(self, f)
+    }
+}
@@ -0,0 +1,121 @@
+use {
+    crate::{MaybeSend, MaybeSync, Mutex, Rc, Weak},
+    alloc::vec::Vec,
+    core::fmt,
+};
+
+/// Shared observer callback subscribed to [`Observers`].
+///
+/// A type alias to `Arc<dyn Fn(&T) + Send + Sync>` when "sync" feature is enabled.\
+/// A type alias to `Rc<dyn Fn(&T)>` when "sync" feature is not enabled.
+///
+/// [`Observers`]: ./struct.Observers.html
+pub type Observer<T> = Rc<crate::dyn_maybe_send_sync!(Fn(&T))>;
+
+type WeakObserver<T> = Weak<crate::dyn_maybe_send_sync!(Fn(&T))>;
+
+/// List of weakly referenced observers.\
+/// Observer stays subscribed as long as [`Observer`] returned from
+/// [`subscribe`] is alive, so observers never keep themselves alive
+/// through reference cycles.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::Observers, std::sync::{Arc, atomic::{AtomicU32, Ordering}}};
+/// let observers = Observers::new();
+/// let sum = Arc::new(AtomicU32::new(0));
+///
+/// let captured = sum.clone();
+/// let subscription = observers.subscribe(move |value: &u32| {
+///     captured.fetch_add(*value, Ordering::Relaxed);
+/// });
+///
+/// observers.notify(&1);
+/// assert_eq!(observers.len(), 1);
+///
+/// // Dropped observer is pruned on next notification.
+/// drop(subscription);
+/// observers.notify(&2);
+/// assert_eq!(observers.len(), 0);
+/// assert_eq!(sum.load(Ordering::Relaxed), 1);
+/// ```
+///
+/// [`Observer`]: ./type.Observer.html
+/// [`subscribe`]: ./struct.Observers.html#method.subscribe
+pub struct Observers<T> {
+    observers: Mutex<Vec<WeakObserver<T>>>,
+}
+
+impl<T> Default for Observers<T> {
+    fn default() -> Self {
+        Observers::new()
+    }
+}
+
+impl<T> Observers<T> {
+    /// Creates new empty list of observers.
+    pub fn new() -> Self {
+        Observers {
+            observers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Subscribes the function.\
+    /// Returns [`Observer`] that keeps the function subscribed while alive.
+    ///
+    /// [`Observer`]: ./type.Observer.html
+    pub fn subscribe(&self, f: impl Fn(&T) + MaybeSend + MaybeSync + 'static) -> Observer<T> {
+        let observer: Observer<T> = Rc::new(f);
+        self.subscribe_observer(&observer);
+        observer
+    }
+
+    /// Subscribes existing observer.
+    pub fn subscribe_observer(&self, observer: &Observer<T>) {
+        self.observers.lock().push(Rc::downgrade(observer));
+    }
+
+    /// Calls all alive observers in order they were subscribed
+    /// and removes dead ones.
+    ///
+    /// Observers are called without holding the lock,
+    /// so they may subscribe new observers.
+    pub fn notify(&self, value: &T) {
+        let alive: Vec<Observer<T>> = {
+            let mut observers = self.observers.lock();
+            let mut alive = Vec::with_capacity(observers.len());
+            observers.retain(|weak| match weak.upgrade() {
+                Some(observer) => {
+                    alive.push(observer);
+                    true
+                }
+                None => false,
+            });
+            alive
+        };
+
+        for observer in alive {
+            observer(value);
+        }
+    }
+
+    /// Returns number of subscribed observers,
+    /// including dead ones that are not pruned yet.
+    pub fn len(&self) -> usize {
+        self.observers.lock().len()
+    }
+
+    /// Returns `true` if there are no subscribed observers.
+    pub fn is_empty(&self) -> bool {
+        self.observers.lock().is_empty()
+    }
+}
+
+impl<T> fmt::Debug for Observers<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observers")
+            .field("len", &self.len())
+            .finish()
+    }
+}
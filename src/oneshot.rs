@@ -0,0 +1,318 @@
+//! One-shot channel for sending single value between tasks.
+//!
+//! [`Sender`] and [`Receiver`] are sendable when "sync" feature is enabled
+//! and `T: Send`.
+//!
+//! [`Receiver`] is a future and can be awaited.
+//! It can also be checked with [`Receiver::try_recv`] in both modes,
+//! or waited on with blocking [`Receiver::recv`] when "sync" feature is enabled.
+//!
+//! # Example
+//!
+//! ```
+//! # use maybe_sync::{future::now_or_never, oneshot};
+//! let (tx, rx) = oneshot::channel();
+//! tx.send(42).unwrap();
+//! assert_eq!(now_or_never(Box::pin(rx)), Some(Ok(42)));
+//! ```
+//!
+//! [`Sender`]: ./struct.Sender.html
+//! [`Receiver`]: ./struct.Receiver.html
+//! [`Receiver::try_recv`]: ./struct.Receiver.html#method.try_recv
+//! [`Receiver::recv`]: ./struct.Receiver.html#method.recv
+
+use {
+    crate::{MaybeSend, Mutex, Rc},
+    core::{
+        fmt::{self, Debug, Display},
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, Waker},
+    },
+};
+
+struct Inner<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+struct Chan<T> {
+    inner: Mutex<Inner<T>>,
+    #[cfg(feature = "sync")]
+    ready: parking_lot::Condvar,
+}
+
+/// Creates new one-shot channel.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>)
+where
+    T: MaybeSend,
+{
+    new()
+}
+
+// Used by `future::remote_handle` that doesn't require sendable output.
+pub(crate) fn new<T>() -> (Sender<T>, Receiver<T>) {
+    let inner = Rc::new(Chan {
+        inner: Mutex::new(Inner {
+            value: None,
+            waker: None,
+            closed: false,
+        }),
+        #[cfg(feature = "sync")]
+        ready: parking_lot::Condvar::new(),
+    });
+
+    (
+        Sender {
+            inner: inner.clone(),
+        },
+        Receiver { inner },
+    )
+}
+
+/// Sending half of one-shot channel.
+pub struct Sender<T> {
+    inner: Rc<Chan<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends value to the [`Receiver`].\
+    /// Returns value back if [`Receiver`] was dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::oneshot;
+    /// let (tx, rx) = oneshot::channel();
+    /// drop(rx);
+    /// assert_eq!(tx.send(42), Err(42));
+    /// ```
+    ///
+    /// [`Receiver`]: ./struct.Receiver.html
+    pub fn send(self, value: T) -> Result<(), T> {
+        let mut inner = self.inner.inner.lock();
+        if inner.closed {
+            return Err(value);
+        }
+        inner.value = Some(value);
+        Ok(())
+    }
+
+    /// Returns `true` if [`Receiver`] was dropped.
+    ///
+    /// [`Receiver`]: ./struct.Receiver.html
+    pub fn is_canceled(&self) -> bool {
+        self.inner.inner.lock().closed
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut inner = self.inner.inner.lock();
+            inner.closed = true;
+            inner.waker.take()
+        };
+
+        #[cfg(feature = "sync")]
+        self.inner.ready.notify_one();
+
+        // Wake outside of the lock, as waker may poll receiver immediately.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("canceled", &self.is_canceled())
+            .finish()
+    }
+}
+
+/// Receiving half of one-shot channel.\
+/// Resolves to value sent by [`Sender`],
+/// or to [`Canceled`] error if [`Sender`] was dropped without sending.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{future::now_or_never, oneshot};
+/// let (tx, rx) = oneshot::channel::<u32>();
+/// drop(tx);
+/// assert_eq!(now_or_never(Box::pin(rx)), Some(Err(oneshot::Canceled)));
+/// ```
+///
+/// Pending receiver is woken exactly once when value is sent.
+///
+/// ```
+/// # use {maybe_sync::future::oneshot, std::{future::Future, pin::Pin, sync::{atomic::{AtomicUsize, Ordering}, Arc}, task::{Context, Poll, Wake, Waker}}};
+/// struct Counter(AtomicUsize);
+///
+/// impl Wake for Counter {
+///     fn wake(self: Arc<Self>) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let counter = Arc::new(Counter(AtomicUsize::new(0)));
+/// let waker = Waker::from(counter.clone());
+/// let mut cx = Context::from_waker(&waker);
+///
+/// let (tx, mut rx) = oneshot::channel();
+/// assert_eq!(Pin::new(&mut rx).poll(&mut cx), Poll::Pending);
+/// tx.send(42).unwrap();
+///
+/// assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+/// assert_eq!(Pin::new(&mut rx).poll(&mut cx), Poll::Ready(Ok(42)));
+/// assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+/// ```
+///
+/// [`Sender`]: ./struct.Sender.html
+/// [`Canceled`]: ./struct.Canceled.html
+pub struct Receiver<T> {
+    inner: Rc<Chan<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive value without blocking.
+    ///
+    /// Returns [`TryRecvError::Empty`] if value was not sent yet,
+    /// or [`TryRecvError::Canceled`] if [`Sender`] was dropped without sending
+    /// or the value was already received.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::oneshot::{self, TryRecvError};
+    /// let (tx, rx) = oneshot::channel();
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    /// tx.send(42).unwrap();
+    /// assert_eq!(rx.try_recv(), Ok(42));
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Canceled));
+    /// ```
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    /// [`TryRecvError::Empty`]: ./enum.TryRecvError.html#variant.Empty
+    /// [`TryRecvError::Canceled`]: ./enum.TryRecvError.html#variant.Canceled
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut inner = self.inner.inner.lock();
+        if let Some(value) = inner.value.take() {
+            Ok(value)
+        } else if inner.closed {
+            Err(TryRecvError::Canceled)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+
+    /// Receives value, blocking current thread until it is sent.\
+    /// Returns [`Canceled`] error if [`Sender`] was dropped without sending.
+    ///
+    /// Available only when "sync" feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::oneshot;
+    /// #[cfg(feature = "sync")]
+    /// {
+    ///     let (tx, rx) = oneshot::channel();
+    ///     std::thread::spawn(move || tx.send(42).unwrap());
+    ///     assert_eq!(rx.recv(), Ok(42));
+    ///
+    ///     let (tx, rx) = oneshot::channel::<u32>();
+    ///     std::thread::spawn(move || drop(tx));
+    ///     assert_eq!(rx.recv(), Err(oneshot::Canceled));
+    /// }
+    /// ```
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    /// [`Canceled`]: ./struct.Canceled.html
+    #[cfg(feature = "sync")]
+    #[cfg_attr(all(doc, feature = "unstable-doc"), doc(cfg(feature = "sync")))]
+    pub fn recv(self) -> Result<T, Canceled> {
+        let mut inner = self.inner.inner.lock();
+        loop {
+            if let Some(value) = inner.value.take() {
+                return Ok(value);
+            }
+            if inner.closed {
+                return Err(Canceled);
+            }
+            self.inner.ready.wait(&mut inner);
+        }
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, Canceled>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, Canceled>> {
+        let mut inner = self.inner.inner.lock();
+        if let Some(value) = inner.value.take() {
+            Poll::Ready(Ok(value))
+        } else if inner.closed {
+            Poll::Ready(Err(Canceled))
+        } else {
+            inner.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.inner.inner.lock().closed = true;
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish()
+    }
+}
+
+/// Error returned from [`Receiver`] when [`Sender`] is dropped without sending value.
+///
+/// [`Sender`]: ./struct.Sender.html
+/// [`Receiver`]: ./struct.Receiver.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Canceled;
+
+impl Display for Canceled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("oneshot canceled")
+    }
+}
+
+impl core::error::Error for Canceled {}
+
+/// Error returned from [`Receiver::try_recv`].
+///
+/// [`Receiver::try_recv`]: ./struct.Receiver.html#method.try_recv
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// Value was not sent yet.
+    Empty,
+
+    /// [`Sender`] was dropped without sending value,
+    /// or the value was already received.
+    ///
+    /// [`Sender`]: ./struct.Sender.html
+    Canceled,
+}
+
+impl Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("oneshot empty"),
+            TryRecvError::Canceled => f.write_str("oneshot canceled"),
+        }
+    }
+}
+
+impl core::error::Error for TryRecvError {}
@@ -0,0 +1,160 @@
+use crate::{MaybeSend, MaybeSync};
+
+#[cfg(all(feature = "sync", feature = "rayon"))]
+use rayon::{
+    iter::{Filter, FromParallelIterator, Map, ParallelIterator},
+    prelude::*,
+    slice::Iter,
+};
+
+#[cfg(not(all(feature = "sync", feature = "rayon")))]
+use core::{
+    iter::{Filter, FromIterator, Map},
+    slice::Iter,
+};
+
+/// Iterator adapter that is parallel when both "sync" and "rayon" features
+/// are enabled, and sequential otherwise.
+///
+/// Closures passed to its methods must be `Fn` and satisfy
+/// [`MaybeSend`] and [`MaybeSync`] bounds, so that the same code compiles
+/// in both configurations.
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`MaybeSync`]: ./trait.MaybeSync.html
+#[derive(Clone, Debug)]
+#[must_use = "iterator adapters are lazy and do nothing unless consumed"]
+pub struct MaybeParIter<I> {
+    iter: I,
+}
+
+/// Returns iterator over the slice that is `rayon::slice::Iter`
+/// when both "sync" and "rayon" features are enabled,
+/// and `core::slice::Iter` otherwise.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::maybe_par_iter;
+/// let values = [1, 2, 3, 4, 5];
+/// let squares: Vec<u32> = maybe_par_iter(&values)
+///     .filter(|&&x| x % 2 == 1)
+///     .map(|&x| x * x)
+///     .collect();
+/// assert_eq!(squares, [1, 9, 25]);
+/// ```
+pub fn maybe_par_iter<T>(slice: &[T]) -> MaybeParIter<Iter<'_, T>>
+where
+    T: MaybeSync,
+{
+    #[cfg(all(feature = "sync", feature = "rayon"))]
+    let iter = slice.par_iter();
+
+    #[cfg(not(all(feature = "sync", feature = "rayon")))]
+    let iter = slice.iter();
+
+    MaybeParIter { iter }
+}
+
+#[cfg(not(all(feature = "sync", feature = "rayon")))]
+impl<I> MaybeParIter<I>
+where
+    I: Iterator,
+{
+    /// Applies the function to each item.
+    pub fn map<F, R>(self, f: F) -> MaybeParIter<Map<I, F>>
+    where
+        F: Fn(I::Item) -> R + MaybeSend + MaybeSync,
+        R: MaybeSend,
+    {
+        MaybeParIter {
+            iter: self.iter.map(f),
+        }
+    }
+
+    /// Retains only items for which predicate returns `true`.
+    pub fn filter<P>(self, predicate: P) -> MaybeParIter<Filter<I, P>>
+    where
+        P: Fn(&I::Item) -> bool + MaybeSend + MaybeSync,
+    {
+        MaybeParIter {
+            iter: self.iter.filter(predicate),
+        }
+    }
+
+    /// Calls the function for each item.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+    #[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+    /// # use maybe_sync::{maybe_par_iter, AtomicUsize};
+    /// let total = AtomicUsize::new(0);
+    /// maybe_par_iter(&[1, 2, 3]).for_each(|&x| {
+    ///     #[cfg(feature = "sync")]
+    ///     total.fetch_add(x, std::sync::atomic::Ordering::Relaxed);
+    ///     #[cfg(not(feature = "sync"))]
+    ///     total.set(total.get() + x);
+    /// });
+    /// assert_eq!(total.into_inner(), 6);
+    /// ```
+    pub fn for_each<F>(self, f: F)
+    where
+        F: Fn(I::Item) + MaybeSend + MaybeSync,
+    {
+        self.iter.for_each(f)
+    }
+
+    /// Collects items into a collection.\
+    /// Order of items is preserved.
+    pub fn collect<C>(self) -> C
+    where
+        C: FromIterator<I::Item>,
+    {
+        self.iter.collect()
+    }
+}
+
+#[cfg(all(feature = "sync", feature = "rayon"))]
+impl<I> MaybeParIter<I>
+where
+    I: ParallelIterator,
+{
+    /// Applies the function to each item.
+    pub fn map<F, R>(self, f: F) -> MaybeParIter<Map<I, F>>
+    where
+        F: Fn(I::Item) -> R + MaybeSend + MaybeSync,
+        R: MaybeSend,
+    {
+        MaybeParIter {
+            iter: self.iter.map(f),
+        }
+    }
+
+    /// Retains only items for which predicate returns `true`.
+    pub fn filter<P>(self, predicate: P) -> MaybeParIter<Filter<I, P>>
+    where
+        P: Fn(&I::Item) -> bool + MaybeSend + MaybeSync,
+    {
+        MaybeParIter {
+            iter: self.iter.filter(predicate),
+        }
+    }
+
+    /// Calls the function for each item.
+    pub fn for_each<F>(self, f: F)
+    where
+        F: Fn(I::Item) + MaybeSend + MaybeSync,
+    {
+        self.iter.for_each(f)
+    }
+
+    /// Collects items into a collection.\
+    /// Order of items is preserved.
+    pub fn collect<C>(self) -> C
+    where
+        C: FromParallelIterator<I::Item>,
+    {
+        self.iter.collect()
+    }
+}
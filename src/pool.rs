@@ -0,0 +1,171 @@
+use {
+    crate::Mutex,
+    alloc::vec::Vec,
+    core::{
+        fmt::{self, Debug},
+        mem::ManuallyDrop,
+        ops::{Deref, DerefMut},
+    },
+};
+
+/// Pool of reusable values.
+///
+/// Values are taken with [`get`] or [`get_or_create`]
+/// and returned to the pool when [`PooledItem`] is dropped.
+///
+/// Wraps `Mutex<Vec<T>>`, so the pool can be shared between threads
+/// when "sync" feature is enabled.
+/// The lock is held only while value is taken or returned.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::Pool;
+/// let pool = Pool::new();
+///
+/// let mut buffer = pool.get_or_create(|| Vec::with_capacity(1024));
+/// buffer.extend_from_slice(b"hello");
+/// assert!(pool.is_empty());
+///
+/// drop(buffer);
+/// assert_eq!(pool.len(), 1);
+///
+/// // Same buffer is reused.
+/// let mut buffer = pool.get_or_create(Vec::new);
+/// assert_eq!(*buffer, b"hello");
+/// assert!(buffer.capacity() >= 1024);
+/// buffer.clear();
+/// ```
+///
+/// [`get`]: ./struct.Pool.html#method.get
+/// [`get_or_create`]: ./struct.Pool.html#method.get_or_create
+/// [`PooledItem`]: ./struct.PooledItem.html
+pub struct Pool<T> {
+    items: Mutex<Vec<T>>,
+}
+
+impl<T> Pool<T> {
+    /// Creates new empty pool.
+    pub fn new() -> Self {
+        Pool {
+            items: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Takes value from the pool or creates new one with provided closure
+    /// if the pool is empty.
+    pub fn get_or_create(&self, create: impl FnOnce() -> T) -> PooledItem<'_, T> {
+        let item = self.items.lock().pop();
+        PooledItem {
+            pool: self,
+            item: ManuallyDrop::new(item.unwrap_or_else(create)),
+        }
+    }
+
+    /// Takes value from the pool or creates default one
+    /// if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::Pool;
+    /// let pool = Pool::<String>::new();
+    /// pool.get().push_str("reused");
+    /// assert_eq!(*pool.get(), "reused");
+    /// ```
+    pub fn get(&self) -> PooledItem<'_, T>
+    where
+        T: Default,
+    {
+        self.get_or_create(T::default)
+    }
+
+    /// Puts value into the pool.
+    pub fn put(&self, item: T) {
+        self.items.lock().push(item);
+    }
+
+    /// Returns number of values in the pool.
+    pub fn len(&self) -> usize {
+        self.items.lock().len()
+    }
+
+    /// Returns `true` if there are no values in the pool.
+    pub fn is_empty(&self) -> bool {
+        self.items.lock().is_empty()
+    }
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Pool::new()
+    }
+}
+
+impl<T> Debug for Pool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool").field("len", &self.len()).finish()
+    }
+}
+
+/// Value taken from [`Pool`].\
+/// Returns the value to the pool on drop.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{Pool, PooledItem};
+/// let pool = Pool::new();
+/// pool.put(1);
+///
+/// let item = pool.get();
+/// assert_eq!(PooledItem::detach(item), 1);
+/// assert!(pool.is_empty());
+/// ```
+///
+/// [`Pool`]: ./struct.Pool.html
+pub struct PooledItem<'a, T> {
+    pool: &'a Pool<T>,
+    item: ManuallyDrop<T>,
+}
+
+impl<T> PooledItem<'_, T> {
+    /// Takes the value out without returning it to the pool.
+    pub fn detach(this: Self) -> T {
+        let mut this = ManuallyDrop::new(this);
+
+        // Safety: `this` is never dropped, so the value is taken only once.
+        unsafe { ManuallyDrop::take(&mut this.item) }
+    }
+}
+
+impl<T> Deref for PooledItem<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T> DerefMut for PooledItem<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T> Drop for PooledItem<'_, T> {
+    fn drop(&mut self) {
+        // Safety: the value is not used after this.
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        self.pool.put(item);
+    }
+}
+
+impl<T> Debug for PooledItem<'_, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.item, f)
+    }
+}
@@ -0,0 +1,549 @@
+//! Free functions operating on [`Rc`] that are spelled the same way
+//! whether "sync" feature is enabled or not.
+//!
+//! Functions that mirror associated functions of `Rc` and `Arc`
+//! also accept std pointer types through [`SharedPtr`] trait.
+//!
+//! # Cyclic construction
+//!
+//! There is no `UniqueRc` whose [`Weak`] pointers become upgradeable
+//! once it is sealed, as stable `Rc` and `Arc` cannot revive
+//! an allocation with zero strong count.
+//! `Rc::new_cyclic` gives the same guarantee for a group of values:
+//! nest one call per value, and every [`Weak`] handed out in between
+//! becomes upgradeable when the outermost call returns.
+//!
+//! ```
+//! # use maybe_sync::{Rc, Weak};
+//! struct Node {
+//!     name: &'static str,
+//!     this: Weak<Node>,
+//!     peer: Weak<Node>,
+//! }
+//!
+//! let mut second = None;
+//! let first = Rc::new_cyclic(|first_weak: &Weak<Node>| {
+//!     let node = Rc::new_cyclic(|second_weak: &Weak<Node>| Node {
+//!         name: "second",
+//!         this: second_weak.clone(),
+//!         peer: first_weak.clone(),
+//!     });
+//!
+//!     // Not sealed yet.
+//!     assert!(node.peer.upgrade().is_none());
+//!
+//!     let peer = Rc::downgrade(&node);
+//!     second = Some(node);
+//!     Node {
+//!         name: "first",
+//!         this: first_weak.clone(),
+//!         peer,
+//!     }
+//! });
+//! let second = second.unwrap();
+//!
+//! // Links in both directions resolve after construction.
+//! assert_eq!(first.peer.upgrade().unwrap().name, "second");
+//! assert_eq!(second.peer.upgrade().unwrap().name, "first");
+//! assert!(Rc::ptr_eq(&first.this.upgrade().unwrap(), &first));
+//! assert!(Rc::ptr_eq(&second.this.upgrade().unwrap(), &second));
+//! ```
+//!
+//! [`Rc`]: ../type.Rc.html
+//! [`Weak`]: ../type.Weak.html
+//! [`SharedPtr`]: ./trait.SharedPtr.html
+
+use {
+    crate::{Rc, Weak},
+    alloc::{boxed::Box, rc::Rc as StdRc, sync::Arc, vec::Vec},
+    core::{cmp::Ordering, ops::Deref},
+};
+
+/// Common interface of reference counted pointers.
+///
+/// Implemented for both `std::rc::Rc` and `std::sync::Arc`,
+/// allowing free functions in this module to accept
+/// either of them in addition to [`Rc`].
+///
+/// [`Rc`]: ../type.Rc.html
+pub trait SharedPtr: Deref + Clone {
+    /// Returns `true` if two pointers point to the same allocation.
+    fn ptr_eq(this: &Self, other: &Self) -> bool;
+
+    /// Returns number of strong pointers to the allocation.
+    fn strong_count(this: &Self) -> usize;
+
+    /// Returns number of weak pointers to the allocation.
+    fn weak_count(this: &Self) -> usize;
+
+    /// Returns mutable reference to the value if there are no other
+    /// strong or weak pointers to the same allocation.
+    fn get_mut(this: &mut Self) -> Option<&mut Self::Target>;
+
+    /// Returns mutable reference to the value, cloning it into new allocation
+    /// if there are other strong or weak pointers to the same allocation.
+    fn make_mut(this: &mut Self) -> &mut Self::Target
+    where
+        Self::Target: Clone;
+
+    /// Returns the inner value if this is the only strong pointer.\
+    /// Otherwise returns the same pointer back.
+    fn try_unwrap(this: Self) -> Result<Self::Target, Self>
+    where
+        Self: Sized,
+        Self::Target: Sized;
+}
+
+macro_rules! impl_shared_ptr {
+    ($ptr:ident) => {
+        impl<T> SharedPtr for $ptr<T>
+        where
+            T: ?Sized,
+        {
+            fn ptr_eq(this: &Self, other: &Self) -> bool {
+                $ptr::ptr_eq(this, other)
+            }
+
+            fn strong_count(this: &Self) -> usize {
+                $ptr::strong_count(this)
+            }
+
+            fn weak_count(this: &Self) -> usize {
+                $ptr::weak_count(this)
+            }
+
+            fn get_mut(this: &mut Self) -> Option<&mut T> {
+                $ptr::get_mut(this)
+            }
+
+            fn make_mut(this: &mut Self) -> &mut T
+            where
+                T: Clone,
+            {
+                $ptr::make_mut(this)
+            }
+
+            fn try_unwrap(this: Self) -> Result<T, Self>
+            where
+                T: Sized,
+            {
+                $ptr::try_unwrap(this)
+            }
+        }
+    };
+}
+
+impl_shared_ptr!(StdRc);
+impl_shared_ptr!(Arc);
+
+/// Returns `true` if two pointers point to the same allocation.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let a = Rc::new(1);
+/// let b = a.clone();
+/// assert!(rc::ptr_eq(&a, &b));
+/// assert!(!rc::ptr_eq(&a, &Rc::new(1)));
+/// ```
+pub fn ptr_eq<P>(this: &P, other: &P) -> bool
+where
+    P: SharedPtr,
+{
+    P::ptr_eq(this, other)
+}
+
+/// Compares two pointers by address of the allocation they point to.
+///
+/// Gives total order consistent with [`ptr_eq`]
+/// that doesn't require `T: Ord`.
+/// The order is stable while the allocations are alive,
+/// but depends on allocation addresses and may differ between runs.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let a = Rc::new("a");
+/// let b = Rc::new("b");
+/// let c = Rc::new("c");
+///
+/// let mut x = vec![c.clone(), a.clone(), b.clone(), a.clone()];
+/// let mut y = vec![b.clone(), a.clone(), c.clone(), a.clone()];
+/// x.sort_by(rc::cmp_by_ptr);
+/// y.sort_by(rc::cmp_by_ptr);
+///
+/// assert!(x.iter().zip(&y).all(|(x, y)| rc::ptr_eq(x, y)));
+///
+/// // Equal pointers end up adjacent.
+/// let i = x.iter().position(|p| rc::ptr_eq(p, &a)).unwrap();
+/// assert!(rc::ptr_eq(&x[i + 1], &a));
+/// ```
+///
+/// [`ptr_eq`]: ./fn.ptr_eq.html
+pub fn cmp_by_ptr<P>(this: &P, other: &P) -> Ordering
+where
+    P: SharedPtr,
+{
+    let this: *const P::Target = &**this;
+    let other: *const P::Target = &**other;
+    this.cast::<()>().cmp(&other.cast::<()>())
+}
+
+/// Returns number of strong pointers to the allocation.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let a = Rc::new(1);
+/// let b = a.clone();
+/// assert_eq!(rc::strong_count(&b), 2);
+///
+/// // Works with pointer types from std too.
+/// assert_eq!(rc::strong_count(&std::rc::Rc::new(1)), 1);
+/// assert_eq!(rc::strong_count(&std::sync::Arc::new(1)), 1);
+/// ```
+pub fn strong_count<P>(this: &P) -> usize
+where
+    P: SharedPtr,
+{
+    P::strong_count(this)
+}
+
+/// Returns number of weak pointers to the allocation.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let a = Rc::new(1);
+/// let _weak = Rc::downgrade(&a);
+/// assert_eq!(rc::weak_count(&a), 1);
+/// ```
+pub fn weak_count<P>(this: &P) -> usize
+where
+    P: SharedPtr,
+{
+    P::weak_count(this)
+}
+
+/// Returns mutable reference to the value if there are no other
+/// strong or weak pointers to the same allocation.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let mut a = Rc::new(1);
+/// *rc::get_mut(&mut a).unwrap() += 1;
+///
+/// let b = a.clone();
+/// assert!(rc::get_mut(&mut a).is_none());
+/// assert_eq!(*b, 2);
+/// ```
+pub fn get_mut<P>(this: &mut P) -> Option<&mut P::Target>
+where
+    P: SharedPtr,
+{
+    P::get_mut(this)
+}
+
+/// Returns mutable reference to the value, cloning it into new allocation
+/// if there are other strong or weak pointers to the same allocation.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let mut a = Rc::new(1);
+/// let b = a.clone();
+/// *rc::make_mut(&mut a) += 1;
+/// assert_eq!(*a, 2);
+/// assert_eq!(*b, 1);
+/// ```
+pub fn make_mut<P>(this: &mut P) -> &mut P::Target
+where
+    P: SharedPtr,
+    P::Target: Clone,
+{
+    P::make_mut(this)
+}
+
+/// Returns the inner value if this is the only strong pointer.\
+/// Otherwise returns the same pointer back.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let a = Rc::new(1);
+/// let b = a.clone();
+/// let a = rc::try_unwrap(a).unwrap_err();
+/// drop(b);
+/// assert_eq!(rc::try_unwrap(a), Ok(1));
+/// ```
+pub fn try_unwrap<P>(this: P) -> Result<P::Target, P>
+where
+    P: SharedPtr,
+    P::Target: Sized,
+{
+    P::try_unwrap(this)
+}
+
+/// Returns `true` if weak pointer points to the same allocation as [`Rc`].
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let a = Rc::new(1);
+/// let weak = Rc::downgrade(&a);
+/// assert!(rc::weak_ptr_eq(&weak, &a));
+/// assert!(!rc::weak_ptr_eq(&weak, &Rc::new(1)));
+/// ```
+///
+/// [`Rc`]: ../type.Rc.html
+pub fn weak_ptr_eq<T>(weak: &Weak<T>, rc: &Rc<T>) -> bool
+where
+    T: ?Sized,
+{
+    core::ptr::eq(weak.as_ptr(), Rc::as_ptr(rc))
+}
+
+/// Increments the strong reference count on the [`Rc`]
+/// associated with the provided pointer by one.
+///
+/// # Safety
+///
+/// The pointer must have been obtained through `Rc::into_raw`,
+/// and the associated [`Rc`] instance must be valid
+/// (i.e. the strong count must be at least 1) for the duration of this method.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let five = Rc::new(5);
+/// unsafe {
+///     let ptr = Rc::into_raw(five);
+///     rc::increment_strong_count(ptr);
+///
+///     let five = Rc::from_raw(ptr);
+///     assert_eq!(rc::strong_count(&five), 2);
+///     rc::decrement_strong_count(ptr);
+///     assert_eq!(rc::strong_count(&five), 1);
+/// }
+/// ```
+///
+/// [`Rc`]: ../type.Rc.html
+pub unsafe fn increment_strong_count<T>(ptr: *const T)
+where
+    T: ?Sized,
+{
+    Rc::increment_strong_count(ptr)
+}
+
+/// Decrements the strong reference count on the [`Rc`]
+/// associated with the provided pointer by one.
+///
+/// # Safety
+///
+/// The pointer must have been obtained through `Rc::into_raw`,
+/// and the associated [`Rc`] instance must be valid
+/// (i.e. the strong count must be at least 1) when invoking this method.\
+/// This method can be used to release the final [`Rc`] and backing storage,
+/// but should not be called after the final [`Rc`] has been released.
+///
+/// [`Rc`]: ../type.Rc.html
+pub unsafe fn decrement_strong_count<T>(ptr: *const T)
+where
+    T: ?Sized,
+{
+    Rc::decrement_strong_count(ptr)
+}
+
+/// Consumes the [`Rc`] returning the wrapped pointer.\
+/// The strong count is not decremented,
+/// use [`from_raw`] to turn the pointer back into [`Rc`]
+/// and release it.
+///
+/// Forwards to `Arc::into_raw` when "sync" feature is enabled.\
+/// Forwards to `Rc::into_raw` when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let ptr = rc::into_raw(Rc::new(String::from("shared")));
+///
+/// // Pass `ptr` through C API as `*const c_void` and get it back.
+/// let ptr = ptr as *const core::ffi::c_void;
+///
+/// let value = unsafe { rc::from_raw(ptr as *const String) };
+/// assert_eq!(*value, "shared");
+/// assert_eq!(rc::strong_count(&value), 1);
+/// ```
+///
+/// [`Rc`]: ../type.Rc.html
+/// [`from_raw`]: ./fn.from_raw.html
+pub fn into_raw<T>(rc: Rc<T>) -> *const T
+where
+    T: ?Sized,
+{
+    Rc::into_raw(rc)
+}
+
+/// Constructs [`Rc`] from pointer returned by [`into_raw`].
+///
+/// Forwards to `Arc::from_raw` when "sync" feature is enabled.\
+/// Forwards to `Rc::from_raw` when "sync" feature is not enabled.
+///
+/// # Safety
+///
+/// The pointer must have been obtained through [`into_raw`]
+/// of the same build of this crate, so that it was created
+/// by the same pointer type from the same allocator.
+/// Pointers obtained from `std::rc::Rc::into_raw` or `std::sync::Arc::into_raw`
+/// are accepted only if that is exactly what [`Rc`] is in the current build.\
+/// Each pointer must be converted back at most once
+/// for every strong count it holds.
+///
+/// `T` must be the type passed to [`into_raw`],
+/// or have the same size and alignment as required by `Rc::from_raw`.
+///
+/// [`Rc`]: ../type.Rc.html
+/// [`into_raw`]: ./fn.into_raw.html
+pub unsafe fn from_raw<T>(ptr: *const T) -> Rc<T>
+where
+    T: ?Sized,
+{
+    Rc::from_raw(ptr)
+}
+
+/// Converts vector into shared slice.
+///
+/// Equivalent to `Arc::from(v)` when "sync" feature is enabled.\
+/// Equivalent to `Rc::from(v)` when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let slice: Rc<[u32]> = rc::from_vec(vec![1, 2, 3]);
+/// assert_eq!(slice.len(), 3);
+/// assert_eq!(&*slice, &[1, 2, 3]);
+/// ```
+pub fn from_vec<T>(v: Vec<T>) -> Rc<[T]> {
+    Rc::from(v)
+}
+
+/// Clones elements of the slice into new shared slice.
+///
+/// Equivalent to `Arc::from(s)` when "sync" feature is enabled.\
+/// Equivalent to `Rc::from(s)` when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc};
+/// let slice: Rc<[String]> = rc::from_slice(&["a".to_owned(), "b".to_owned()]);
+/// assert_eq!(slice.len(), 2);
+/// assert_eq!(&*slice, &["a", "b"]);
+/// ```
+pub fn from_slice<T>(s: &[T]) -> Rc<[T]>
+where
+    T: Clone,
+{
+    Rc::from(s)
+}
+
+/// Conversion into [`Rc`] from other pointer types and values.
+///
+/// Conversion cost depends on the source and "sync" feature:
+///
+/// * `T` and `Box<T>` are moved into new allocation.
+/// * `Arc<T>` is returned as is when "sync" feature is enabled.
+///   Otherwise the value is moved out if this is the only strong pointer
+///   or cloned into new allocation, which is why `T: Clone` is required.
+/// * `std::rc::Rc<T>` is returned as is.
+///   Available only when "sync" feature is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{rc::{self, IntoMaybeRc}, Rc}, std::sync::Arc};
+/// let arc = Arc::new(String::from("asset"));
+/// let rc: Rc<String> = arc.into_maybe_rc();
+/// assert_eq!(*rc, "asset");
+///
+/// let boxed: Rc<[u8]> = rc::from_shared(vec![1, 2, 3].into_boxed_slice());
+/// assert_eq!(&*boxed, &[1, 2, 3]);
+///
+/// let value: Rc<u32> = rc::from_shared(42);
+/// assert_eq!(*value, 42);
+/// ```
+///
+/// [`Rc`]: ../type.Rc.html
+pub trait IntoMaybeRc<T: ?Sized> {
+    /// Performs conversion.
+    fn into_maybe_rc(self) -> Rc<T>;
+}
+
+impl<T> IntoMaybeRc<T> for T {
+    fn into_maybe_rc(self) -> Rc<T> {
+        Rc::new(self)
+    }
+}
+
+impl<T> IntoMaybeRc<T> for Box<T>
+where
+    T: ?Sized,
+{
+    fn into_maybe_rc(self) -> Rc<T> {
+        Rc::from(self)
+    }
+}
+
+#[cfg(feature = "sync")]
+impl<T> IntoMaybeRc<T> for Arc<T>
+where
+    T: ?Sized,
+{
+    fn into_maybe_rc(self) -> Rc<T> {
+        self
+    }
+}
+
+#[cfg(not(feature = "sync"))]
+impl<T> IntoMaybeRc<T> for Arc<T>
+where
+    T: Clone,
+{
+    fn into_maybe_rc(self) -> Rc<T> {
+        Rc::new(Arc::try_unwrap(self).unwrap_or_else(|arc| T::clone(&arc)))
+    }
+}
+
+#[cfg(not(feature = "sync"))]
+impl<T> IntoMaybeRc<T> for StdRc<T>
+where
+    T: ?Sized,
+{
+    fn into_maybe_rc(self) -> Rc<T> {
+        self
+    }
+}
+
+/// Converts value or pointer into [`Rc`].\
+/// See [`IntoMaybeRc`] for conversion costs.
+///
+/// [`Rc`]: ../type.Rc.html
+/// [`IntoMaybeRc`]: ./trait.IntoMaybeRc.html
+pub fn from_shared<T>(value: impl IntoMaybeRc<T>) -> Rc<T>
+where
+    T: ?Sized,
+{
+    value.into_maybe_rc()
+}
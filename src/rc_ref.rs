@@ -0,0 +1,95 @@
+use {
+    crate::Rc,
+    core::{fmt, ops::Deref},
+};
+
+/// Borrowed [`Rc`] that derefs to the value without touching reference counter.
+///
+/// Can be turned into owned [`Rc`] with [`to_owned`] when needed.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Rc, RcRef};
+/// fn frame(scene: RcRef<'_, String>) -> Option<Rc<String>> {
+///     if scene.is_empty() {
+///         None
+///     } else {
+///         Some(scene.to_owned())
+///     }
+/// }
+///
+/// let scene = Rc::new(String::from("scene"));
+/// assert!(frame(RcRef::from(&scene)).is_some());
+/// assert_eq!(rc::strong_count(&scene), 1);
+/// ```
+///
+/// [`Rc`]: ./type.Rc.html
+/// [`to_owned`]: ./struct.RcRef.html#method.to_owned
+pub struct RcRef<'a, T: ?Sized> {
+    rc: &'a Rc<T>,
+}
+
+impl<'a, T> RcRef<'a, T>
+where
+    T: ?Sized,
+{
+    /// Returns new owned [`Rc`] pointing to the same allocation.
+    ///
+    /// [`Rc`]: ./type.Rc.html
+    pub fn to_owned(self) -> Rc<T> {
+        self.rc.clone()
+    }
+
+    /// Returns borrowed [`Rc`].
+    ///
+    /// [`Rc`]: ./type.Rc.html
+    pub fn as_rc(self) -> &'a Rc<T> {
+        self.rc
+    }
+
+    /// Returns reference to the value with lifetime of the borrow.
+    pub fn get(self) -> &'a T {
+        self.rc
+    }
+}
+
+impl<T> Clone for RcRef<'_, T>
+where
+    T: ?Sized,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RcRef<'_, T> where T: ?Sized {}
+
+impl<'a, T> From<&'a Rc<T>> for RcRef<'a, T>
+where
+    T: ?Sized,
+{
+    fn from(rc: &'a Rc<T>) -> Self {
+        RcRef { rc }
+    }
+}
+
+impl<T> Deref for RcRef<'_, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.rc
+    }
+}
+
+impl<T> fmt::Debug for RcRef<'_, T>
+where
+    T: fmt::Debug + ?Sized,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, fmt)
+    }
+}
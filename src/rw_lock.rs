@@ -0,0 +1,49 @@
+use crate::RwLock;
+
+/// Extension methods for [`RwLock`] that work the same way
+/// whether "sync" feature is enabled or not.
+///
+/// [`RwLock`]: ./type.RwLock.html
+pub trait RwLockExt<T: ?Sized> {
+    /// Acquires shared read access and calls provided closure
+    /// with reference to the locked value.\
+    /// The lock is released before this function returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{RwLock, RwLockExt};
+    /// let lock = RwLock::new(vec![1, 2, 3]);
+    /// assert_eq!(lock.read_with(|v| v.len()), 3);
+    /// ```
+    fn read_with<R>(&self, f: impl FnOnce(&T) -> R) -> R;
+
+    /// Acquires exclusive write access and calls provided closure
+    /// with mutable reference to the locked value.\
+    /// The lock is released before this function returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use maybe_sync::{RwLock, RwLockExt};
+    /// let lock = RwLock::new(vec![1, 2, 3]);
+    /// lock.write_with(|v| v.push(4));
+    /// assert_eq!(*lock.read(), [1, 2, 3, 4]);
+    /// ```
+    fn write_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R;
+}
+
+impl<T> RwLockExt<T> for RwLock<T>
+where
+    T: ?Sized,
+{
+    #[track_caller]
+    fn read_with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&*self.read())
+    }
+
+    #[track_caller]
+    fn write_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut *self.write())
+    }
+}
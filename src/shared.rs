@@ -0,0 +1,74 @@
+use crate::{Mutex, Rc, RwLock, Weak};
+
+/// Shared pointer to a value protected by [`Mutex`].
+///
+/// A type alias to `Arc<parking_lot::Mutex<T>>` when "sync" feature is enabled.\
+/// A type alias to `Rc<maybe_sync::Mutex<T>>` when "sync" feature is not enabled.
+///
+/// [`Mutex`]: ./type.Mutex.html
+pub type Shared<T> = Rc<Mutex<T>>;
+
+/// Non-owning handle to the value owned by [`Shared`].\
+/// Created by `Shared::downgrade` and upgraded back with `SharedWeak::upgrade`.
+///
+/// Upgrading returns `None` once the last [`Shared`] handle is dropped.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{rc, Mutex, Shared, SharedWeak};
+/// let model: Shared<u32> = Shared::new(Mutex::new(1));
+/// let observer: SharedWeak<u32> = Shared::downgrade(&model);
+///
+/// assert!(SharedWeak::ptr_eq(&observer, &observer.clone()));
+/// assert!(rc::weak_ptr_eq(&observer, &model));
+///
+/// *observer.upgrade().unwrap().lock() += 1;
+/// assert_eq!(*model.lock(), 2);
+///
+/// drop(model);
+/// assert!(observer.upgrade().is_none());
+/// ```
+///
+/// [`Shared`]: ./type.Shared.html
+pub type SharedWeak<T> = Weak<Mutex<T>>;
+
+/// Shared pointer to a value protected by [`RwLock`].\
+/// Suits read-mostly state, as readers do not block each other
+/// when "sync" feature is enabled.
+///
+/// A type alias to `Arc<parking_lot::RwLock<T>>` when "sync" feature is enabled.\
+/// A type alias to `Rc<maybe_sync::RwLock<T>>` when "sync" feature is not enabled.
+///
+/// Use [`RwLockExt`] methods to access the value without handling guards.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{shared_rw, RwLockExt, SharedRw};
+/// let config: SharedRw<Vec<&str>> = shared_rw(vec!["a", "b"]);
+///
+/// #[cfg(feature = "sync")]
+/// std::thread::scope(|scope| {
+///     let readers: Vec<_> = (0..4)
+///         .map(|_| scope.spawn(|| config.read_with(|names| names.len())))
+///         .collect();
+///     for reader in readers {
+///         assert_eq!(reader.join().unwrap(), 2);
+///     }
+/// });
+///
+/// config.write_with(|names| names.push("c"));
+/// assert_eq!(config.read_with(|names| names.join("")), "abc");
+/// ```
+///
+/// [`RwLock`]: ./type.RwLock.html
+/// [`RwLockExt`]: ./trait.RwLockExt.html
+pub type SharedRw<T> = Rc<RwLock<T>>;
+
+/// Creates new [`SharedRw`] holding the value.
+///
+/// [`SharedRw`]: ./type.SharedRw.html
+pub fn shared_rw<T>(value: T) -> SharedRw<T> {
+    Rc::new(RwLock::new(value))
+}
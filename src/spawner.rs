@@ -0,0 +1,92 @@
+use {
+    crate::{task::Spawn, MaybeSend, MaybeSync},
+    alloc::boxed::Box,
+    core::future::Future,
+};
+
+type Spawner = Box<crate::dyn_maybe_send_sync!(Spawn)>;
+
+#[cfg(feature = "sync")]
+static SPAWNER: std::sync::OnceLock<Spawner> = std::sync::OnceLock::new();
+
+#[cfg(not(feature = "sync"))]
+std::thread_local! {
+    static SPAWNER: core::cell::OnceCell<Spawner> = const { core::cell::OnceCell::new() };
+}
+
+#[cfg(feature = "sync")]
+fn with_spawner<R>(f: impl FnOnce(&std::sync::OnceLock<Spawner>) -> R) -> R {
+    f(&SPAWNER)
+}
+
+#[cfg(not(feature = "sync"))]
+fn with_spawner<R>(f: impl FnOnce(&core::cell::OnceCell<Spawner>) -> R) -> R {
+    SPAWNER.with(f)
+}
+
+/// Registers spawner used by [`spawn`].\
+/// Returns the spawner back if one was already registered.
+///
+/// Any [`Spawn`] implementation can be registered,
+/// including functions and closures taking [`BoxFuture`].
+///
+/// When "sync" feature is enabled the spawner is registered for the whole process
+/// and must be `Send + Sync`.\
+/// When "sync" feature is not enabled the spawner is registered
+/// for the current thread only, as neither the spawner nor spawned futures
+/// can leave the thread.
+///
+/// # Example
+///
+#[cfg_attr(all(feature = "strict", not(feature = "sync")), doc = "```ignore")]
+#[cfg_attr(not(all(feature = "strict", not(feature = "sync"))), doc = "```")]
+/// # use maybe_sync::{future::now_or_never, set_global_spawner, spawn, BoxFuture, Mutex, Rc};
+/// let spawner = |f: BoxFuture<'static, ()>| {
+///     now_or_never(f);
+/// };
+/// assert!(set_global_spawner(spawner).is_ok());
+/// assert!(set_global_spawner(spawner).is_err());
+///
+/// let log = Rc::new(Mutex::new(Vec::new()));
+/// let task_log = log.clone();
+/// spawn(async move { task_log.lock().push(42) });
+/// assert_eq!(*log.lock(), [42]);
+/// ```
+///
+/// [`spawn`]: ./fn.spawn.html
+/// [`Spawn`]: ./task/trait.Spawn.html
+/// [`BoxFuture`]: ./type.BoxFuture.html
+pub fn set_global_spawner<S>(spawner: S) -> Result<(), S>
+where
+    S: Spawn + MaybeSend + MaybeSync + 'static,
+{
+    let mut spawner = Some(spawner);
+    with_spawner(|cell| {
+        cell.get_or_init(|| Box::new(spawner.take().unwrap()));
+    });
+    match spawner {
+        None => Ok(()),
+        Some(spawner) => Err(spawner),
+    }
+}
+
+/// Spawns the future using spawner registered with [`set_global_spawner`].
+///
+/// The future must be `Send` when "sync" feature is enabled.
+///
+/// # Panics
+///
+/// Panics if no spawner was registered.
+///
+/// [`set_global_spawner`]: ./fn.set_global_spawner.html
+#[track_caller]
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + MaybeSend + 'static,
+{
+    let future = Box::pin(future);
+    with_spawner(|cell| match cell.get() {
+        Some(spawner) => spawner.spawn(future),
+        None => panic!("Global spawner is not registered"),
+    })
+}
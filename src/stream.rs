@@ -0,0 +1,137 @@
+use {alloc::boxed::Box, core::pin::Pin, futures_core::Stream};
+
+use crate::MaybeSend;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An owned dynamically typed [`Stream`].
+///
+/// A type alias equal to `futures::stream::BoxStream`
+/// when "sync" feature is enabled.\
+/// A type alias equal to `futures::stream::LocalBoxStream`
+/// when "sync" feature is not enabled.
+///
+/// [`Stream`]: https://docs.rs/futures-core/0.3/futures_core/stream/trait.Stream.html
+pub type BoxStream<'a, T> = Pin<Box<crate::dyn_maybe_send!(Stream<Item = T> + 'a)>>;
+
+/// Extension trait for streams to box them into [`BoxStream`].
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{BoxStream, MaybeSendStreamExt}, futures_core::Stream, std::{pin::Pin, task::{Context, Poll, Waker}}};
+/// struct Countdown(u32);
+///
+/// impl Stream for Countdown {
+///     type Item = u32;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u32>> {
+///         if self.0 == 0 {
+///             Poll::Ready(None)
+///         } else {
+///             self.0 -= 1;
+///             Poll::Ready(Some(self.0))
+///         }
+///     }
+/// }
+///
+/// let mut stream: BoxStream<'static, u32> = Countdown(2).boxed_maybe();
+/// let mut cx = Context::from_waker(Waker::noop());
+/// assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(1)));
+/// assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(0)));
+/// assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Ready(None));
+/// ```
+///
+/// Non-sendable streams can be boxed only when "sync" feature is not enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::MaybeSendStreamExt, futures_core::Stream, std::{pin::Pin, rc::Rc, task::{Context, Poll}}};
+/// struct Local(Rc<u32>);
+///
+/// impl Stream for Local {
+///     type Item = u32;
+///
+///     fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u32>> {
+///         Poll::Ready(Some(*self.0))
+///     }
+/// }
+///
+/// let _stream = Local(Rc::new(42)).boxed_maybe();
+/// ```
+///
+/// [`BoxStream`]: ./type.BoxStream.html
+pub trait MaybeSendStreamExt: Stream {
+    /// Boxes the stream into [`BoxStream`].
+    ///
+    /// [`BoxStream`]: ./type.BoxStream.html
+    fn boxed_maybe<'a>(self) -> BoxStream<'a, Self::Item>
+    where
+        Self: Sized + MaybeSend + 'a,
+    {
+        Box::pin(self)
+    }
+}
+
+impl<S> MaybeSendStreamExt for S where S: Stream + ?Sized {}
+
+/// Shorthand for `Stream + MaybeSend` bound.\
+/// Sealed and implemented for all streams that satisfy [`MaybeSend`].
+///
+/// It is intended to be used as trait bound only, not as trait object type.
+/// `Item` associated type is inherited from [`Stream`]
+/// and can be constrained as usual.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::MaybeSendStream, futures_core::Stream, std::{pin::Pin, task::{Context, Poll}}};
+/// struct Once(Option<u32>);
+///
+/// impl Stream for Once {
+///     type Item = u32;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u32>> {
+///         Poll::Ready(self.0.take())
+///     }
+/// }
+///
+/// fn first<S: MaybeSendStream<Item = u32> + Unpin>(mut s: S) -> Option<u32> {
+///     let mut cx = Context::from_waker(std::task::Waker::noop());
+///     match Pin::new(&mut s).poll_next(&mut cx) {
+///         Poll::Ready(item) => item,
+///         Poll::Pending => None,
+///     }
+/// }
+///
+/// assert_eq!(first(Once(Some(42))), Some(42));
+/// ```
+///
+/// Non-sendable streams satisfy this bound only when "sync" feature is not enabled.
+///
+#[cfg_attr(any(feature = "sync", feature = "strict"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "strict")), doc = "```")]
+/// # use {maybe_sync::MaybeSendStream, futures_core::Stream, std::{pin::Pin, rc::Rc, task::{Context, Poll}}};
+/// struct Local(Rc<u32>);
+///
+/// impl Stream for Local {
+///     type Item = u32;
+///
+///     fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u32>> {
+///         Poll::Ready(Some(*self.0))
+///     }
+/// }
+///
+/// fn consume<S: MaybeSendStream<Item = u32>>(_: S) {}
+///
+/// consume(Local(Rc::new(42)));
+/// ```
+///
+/// [`MaybeSend`]: ./trait.MaybeSend.html
+/// [`Stream`]: https://docs.rs/futures-core/0.3/futures_core/stream/trait.Stream.html
+pub trait MaybeSendStream: Stream + MaybeSend + sealed::Sealed {}
+
+impl<S> sealed::Sealed for S where S: Stream + MaybeSend + ?Sized {}
+impl<S> MaybeSendStream for S where S: Stream + MaybeSend + ?Sized {}
@@ -0,0 +1,206 @@
+//! Executor abstraction shared by libraries
+//! whether "sync" feature is enabled or not.
+//!
+//! Libraries that drive background work accept a [`Spawn`] handle
+//! instead of defining their own spawn trait,
+//! so one executor can be shared between all of them.
+//!
+//! [`Spawn`]: ./trait.Spawn.html
+
+use {
+    crate::{BoxFuture, Mutex, Rc},
+    alloc::{boxed::Box, vec::Vec},
+    core::{
+        fmt::{self, Debug},
+        mem,
+        task::{Context, Waker},
+    },
+};
+
+/// Executor that runs futures in background.
+///
+/// Takes [`BoxFuture`], so when "sync" feature is enabled
+/// spawned futures are sendable and multithreaded executors can be used,
+/// and without it non-sendable futures can be spawned
+/// on single-threaded executors like `wasm_bindgen_futures::spawn_local`.
+///
+/// Implemented for functions and closures taking [`BoxFuture`],
+/// for [`Rc`] of any implementation,
+/// and for references and boxes of `dyn Spawn` and `dyn Spawn + Send + Sync`.
+///
+/// The trait is dyn compatible.
+/// Use `dyn_maybe_send_sync!(Spawn)` for handles that are
+/// sendable when "sync" feature is enabled.\
+/// [`set_global_spawner`] accepts any implementation of this trait.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{dyn_maybe_send_sync, task::{InlineExecutor, Spawn}, BoxFuture, Mutex, Rc};
+/// struct Loader {
+///     executor: Rc<dyn_maybe_send_sync!(Spawn)>,
+///     loaded: Rc<Mutex<Vec<u32>>>,
+/// }
+///
+/// impl Loader {
+///     fn load(&self, id: u32) {
+///         let loaded = self.loaded.clone();
+///         self.executor.spawn(Box::pin(async move { loaded.lock().push(id) }));
+///     }
+/// }
+///
+/// let executor = Rc::new(InlineExecutor::new());
+/// let loader = Loader {
+///     executor: executor.clone(),
+///     loaded: Rc::new(Mutex::new(Vec::new())),
+/// };
+/// loader.load(1);
+/// loader.load(2);
+/// assert_eq!(executor.run_until_stalled(), 0);
+/// assert_eq!(*loader.loaded.lock(), [1, 2]);
+/// ```
+///
+/// Closures work as executors.
+///
+/// ```
+/// # use maybe_sync::{future::now_or_never, task::Spawn, BoxFuture};
+/// fn run(executor: impl Spawn) {
+///     executor.spawn(Box::pin(async { println!("spawned") }));
+/// }
+///
+/// run(|future: BoxFuture<'static, ()>| {
+///     now_or_never(future);
+/// });
+///
+/// let boxed: Box<dyn Spawn> = Box::new(|future: BoxFuture<'static, ()>| drop(future));
+/// run(&*boxed);
+/// run(boxed);
+/// ```
+///
+/// [`BoxFuture`]: ../type.BoxFuture.html
+/// [`Rc`]: ../type.Rc.html
+/// [`set_global_spawner`]: ../fn.set_global_spawner.html
+pub trait Spawn {
+    /// Spawns the future to run to completion in background.
+    fn spawn(&self, future: BoxFuture<'static, ()>);
+}
+
+impl<F> Spawn for F
+where
+    F: Fn(BoxFuture<'static, ()>),
+{
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self(future)
+    }
+}
+
+impl<T> Spawn for Rc<T>
+where
+    T: Spawn + ?Sized,
+{
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        T::spawn(self, future)
+    }
+}
+
+// Generic impls for `&T` and `Box<T>` would overlap with the one for closures,
+// so only trait objects are covered.
+macro_rules! impl_spawn_for_dyn {
+    ($($dyn:tt)*) => {
+        impl<'a> Spawn for &'a ($($dyn)* + 'a) {
+            fn spawn(&self, future: BoxFuture<'static, ()>) {
+                (**self).spawn(future)
+            }
+        }
+
+        impl<'a> Spawn for Box<$($dyn)* + 'a> {
+            fn spawn(&self, future: BoxFuture<'static, ()>) {
+                (**self).spawn(future)
+            }
+        }
+    };
+}
+
+impl_spawn_for_dyn!(dyn Spawn);
+impl_spawn_for_dyn!(dyn Spawn + Send + Sync);
+
+/// Trivial executor for tests.
+///
+/// Polls spawned future immediately and keeps it if it is pending.
+/// Pending futures are polled again with [`run_until_stalled`].\
+/// Wakeups are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{future::oneshot, task::{InlineExecutor, Spawn}, Mutex, Rc};
+/// let executor = InlineExecutor::new();
+/// let (tx, rx) = oneshot::channel();
+/// let received = Rc::new(Mutex::new(None));
+///
+/// let task_received = received.clone();
+/// executor.spawn(Box::pin(async move {
+///     *task_received.lock() = rx.await.ok();
+/// }));
+/// assert_eq!(executor.run_until_stalled(), 1);
+///
+/// tx.send(42).unwrap();
+/// assert_eq!(executor.run_until_stalled(), 0);
+/// assert_eq!(*received.lock(), Some(42));
+/// ```
+///
+/// [`run_until_stalled`]: ./struct.InlineExecutor.html#method.run_until_stalled
+#[derive(Default)]
+pub struct InlineExecutor {
+    pending: Mutex<Vec<BoxFuture<'static, ()>>>,
+}
+
+impl InlineExecutor {
+    /// Creates new executor without pending futures.
+    pub fn new() -> Self {
+        InlineExecutor {
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Polls pending futures until none of them completes in a whole pass.\
+    /// Returns number of futures that are still pending.
+    pub fn run_until_stalled(&self) -> usize {
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            // Lock is released while polling, so futures can spawn more.
+            let futures = mem::take(&mut *self.pending.lock());
+            let count = futures.len();
+            let mut still_pending = Vec::with_capacity(count);
+            for mut future in futures {
+                if future.as_mut().poll(&mut cx).is_pending() {
+                    still_pending.push(future);
+                }
+            }
+
+            let stalled = still_pending.len() == count;
+            let mut pending = self.pending.lock();
+            pending.extend(still_pending);
+            if stalled {
+                return pending.len();
+            }
+        }
+    }
+}
+
+impl Spawn for InlineExecutor {
+    fn spawn(&self, mut future: BoxFuture<'static, ()>) {
+        let mut cx = Context::from_waker(Waker::noop());
+        if future.as_mut().poll(&mut cx).is_pending() {
+            self.pending.lock().push(future);
+        }
+    }
+}
+
+impl Debug for InlineExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InlineExecutor")
+            .field("pending", &self.pending.lock().len())
+            .finish()
+    }
+}
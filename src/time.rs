@@ -0,0 +1,205 @@
+//! Monotonic clock and sleep that work the same way
+//! in native and browser environments.
+//!
+//! With "std" feature on non-wasm targets [`now`] reads `std::time::Instant`,
+//! unless host registered its own clock with [`set_clock`].\
+//! Otherwise, e.g. on `wasm32-unknown-unknown`, host must register one,
+//! wrapping `performance.now()` for instance.
+//!
+//! [`sleep`] uses timer registered with [`set_timer`],
+//! falling back to [`TokioDelay`] or [`GlooDelay`] enabled with "tokio"
+//! or "gloo-timers" features respectively.
+//!
+//! Sources should be registered once at startup,
+//! as instants read from different clocks are not comparable.
+//!
+//! [`now`]: ./fn.now.html
+//! [`sleep`]: ./fn.sleep.html
+//! [`set_clock`]: ./fn.set_clock.html
+//! [`set_timer`]: ./fn.set_timer.html
+//! [`TokioDelay`]: ../future/struct.TokioDelay.html
+//! [`GlooDelay`]: ../future/struct.GlooDelay.html
+
+pub use crate::future::{set_timer, Timer};
+
+use {
+    crate::BoxFuture,
+    core::{
+        ops::{Add, Sub},
+        ptr,
+        sync::atomic::{AtomicPtr, Ordering},
+        time::Duration,
+    },
+};
+
+/// Function that returns monotonic time elapsed since arbitrary fixed point.
+///
+/// Host application provides one with [`set_clock`].
+///
+/// [`set_clock`]: ./fn.set_clock.html
+pub type Clock = fn() -> Duration;
+
+static CLOCK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets clock used by [`now`].
+///
+/// [`now`]: ./fn.now.html
+pub fn set_clock(clock: Clock) {
+    CLOCK.store(clock as *mut (), Ordering::Release);
+}
+
+fn clock() -> Option<Clock> {
+    let ptr = CLOCK.load(Ordering::Acquire);
+    if ptr.is_null() {
+        None
+    } else {
+        // Safety: only `Clock` function pointers are stored in `CLOCK`.
+        Some(unsafe { core::mem::transmute::<*mut (), Clock>(ptr) })
+    }
+}
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+fn std_clock() -> Duration {
+    static ORIGIN: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    ORIGIN.get_or_init(std::time::Instant::now).elapsed()
+}
+
+/// Measurement of monotonic clock.\
+/// Opaque and useful only with [`Duration`].
+///
+/// [`Duration`]: https://doc.rust-lang.org/core/time/struct.Duration.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant {
+    since_origin: Duration,
+}
+
+impl Instant {
+    /// Returns current instant, same as [`now`].
+    ///
+    /// [`now`]: ./fn.now.html
+    #[track_caller]
+    pub fn now() -> Self {
+        now()
+    }
+
+    /// Returns time elapsed since `earlier` instant,
+    /// or zero if `earlier` is later than this one.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        self.since_origin.saturating_sub(earlier.since_origin)
+    }
+
+    /// Returns time elapsed since this instant.
+    #[track_caller]
+    pub fn elapsed(&self) -> Duration {
+        now().duration_since(*self)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Instant {
+            since_origin: self.since_origin + rhs,
+        }
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Instant) -> Duration {
+        self.duration_since(rhs)
+    }
+}
+
+/// Returns current instant of the monotonic clock.
+///
+/// # Panics
+///
+/// Panics if clock was not set with [`set_clock`]
+/// and there is no default one for the target.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::time::{now, set_clock}, std::time::Duration};
+/// // Default clock exists only with "std" feature on native targets.
+/// #[cfg(not(feature = "std"))]
+/// set_clock(|| Duration::from_millis(42));
+///
+/// let start = now();
+/// assert!(now() >= start);
+/// assert_eq!((start + Duration::from_secs(1)) - start, Duration::from_secs(1));
+/// ```
+///
+/// [`set_clock`]: ./fn.set_clock.html
+#[track_caller]
+pub fn now() -> Instant {
+    let since_origin = match clock() {
+        Some(clock) => clock(),
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+        None => std_clock(),
+        #[cfg(not(all(feature = "std", not(target_arch = "wasm32"))))]
+        None => panic!("Clock must be set with `maybe_sync::time::set_clock`"),
+    };
+    Instant { since_origin }
+}
+
+/// Returns future which resolves after specified duration.
+///
+/// Uses timer registered with [`set_timer`].
+/// Falls back to [`TokioDelay`] with "tokio" feature
+/// and to [`GlooDelay`] with "gloo-timers" feature.
+/// If both are enabled, [`GlooDelay`] is used on wasm targets.
+///
+/// # Panics
+///
+/// Panics if timer was not set with [`set_timer`]
+/// and neither "tokio" nor "gloo-timers" feature is enabled.\
+/// Returned future panics if [`TokioDelay`] is used outside of tokio runtime.
+///
+/// # Example
+///
+/// ```
+/// # use {maybe_sync::{future::now_or_never, time::{set_timer, sleep}}, std::time::Duration};
+/// // Timer that is elapsed immediately.
+/// // Applications usually wrap `tokio::time::sleep` or `gloo_timers::future::sleep`.
+/// set_timer(|_| Box::pin(async {}));
+///
+/// assert_eq!(now_or_never(sleep(Duration::from_secs(1))), Some(()));
+/// ```
+///
+/// [`set_timer`]: ./fn.set_timer.html
+/// [`TokioDelay`]: ../future/struct.TokioDelay.html
+/// [`GlooDelay`]: ../future/struct.GlooDelay.html
+#[track_caller]
+pub fn sleep(dur: Duration) -> BoxFuture<'static, ()> {
+    match crate::future::timer() {
+        Some(timer) => timer(dur),
+        None => fallback_sleep(dur),
+    }
+}
+
+#[cfg(all(
+    feature = "gloo-timers",
+    not(feature = "sync"),
+    any(target_arch = "wasm32", not(feature = "tokio"))
+))]
+fn fallback_sleep(dur: Duration) -> BoxFuture<'static, ()> {
+    crate::future::Delay::delay(&crate::future::GlooDelay, dur)
+}
+
+#[cfg(all(
+    feature = "tokio",
+    not(all(feature = "gloo-timers", not(feature = "sync"), target_arch = "wasm32"))
+))]
+fn fallback_sleep(dur: Duration) -> BoxFuture<'static, ()> {
+    crate::future::Delay::delay(&crate::future::TokioDelay, dur)
+}
+
+#[cfg(not(any(feature = "tokio", all(feature = "gloo-timers", not(feature = "sync")))))]
+#[track_caller]
+fn fallback_sleep(_dur: Duration) -> BoxFuture<'static, ()> {
+    panic!("Timer must be set with `maybe_sync::future::set_timer`")
+}
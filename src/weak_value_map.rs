@@ -0,0 +1,104 @@
+use {
+    crate::{Mutex, Rc, RcRef, Weak},
+    alloc::collections::BTreeMap,
+    core::borrow::Borrow,
+};
+
+/// Map from keys to weak pointers to values.
+///
+/// Entries which values were dropped are evicted lazily on access.\
+/// The map is protected by [`Mutex`], so it can be shared between threads
+/// when "sync" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_sync::{Rc, WeakValueMap};
+/// let cache = WeakValueMap::new();
+///
+/// let texture = Rc::new("texture data");
+/// cache.insert("texture", &texture);
+/// assert_eq!(cache.get("texture").as_deref(), Some(&"texture data"));
+///
+/// // Value is dropped between insertion and lookup.
+/// drop(texture);
+/// assert!(cache.get("texture").is_none());
+/// assert_eq!(cache.len_live(), 0);
+/// ```
+///
+/// [`Mutex`]: ./type.Mutex.html
+pub struct WeakValueMap<K, V> {
+    map: Mutex<BTreeMap<K, Weak<V>>>,
+}
+
+impl<K, V> Default for WeakValueMap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        WeakValueMap::new()
+    }
+}
+
+impl<K, V> WeakValueMap<K, V>
+where
+    K: Ord,
+{
+    /// Creates new empty map.
+    pub fn new() -> Self {
+        WeakValueMap {
+            map: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns value associated with the key if it is still alive.\
+    /// Evicts the entry if value was dropped.
+    pub fn get<Q>(&self, key: &Q) -> Option<Rc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut map = self.map.lock();
+        let value = map.get(key)?.upgrade();
+        if value.is_none() {
+            map.remove(key);
+        }
+        value
+    }
+
+    /// Associates weak pointer to the value with the key.\
+    /// Returns previously associated value if it is still alive.
+    pub fn insert<'a>(&self, key: K, value: impl Into<RcRef<'a, V>>) -> Option<Rc<V>>
+    where
+        V: 'a,
+    {
+        self.map
+            .lock()
+            .insert(key, Rc::downgrade(value.into().as_rc()))
+            .and_then(|weak| weak.upgrade())
+    }
+
+    /// Removes the entry from the map.\
+    /// Returns associated value if it is still alive.
+    pub fn remove<Q>(&self, key: &Q) -> Option<Rc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.lock().remove(key).and_then(|weak| weak.upgrade())
+    }
+
+    /// Evicts all entries which values were dropped.
+    pub fn retain_live(&self) {
+        self.map.lock().retain(|_, weak| weak.strong_count() > 0);
+    }
+
+    /// Returns number of entries which values are still alive.
+    pub fn len_live(&self) -> usize {
+        self.map
+            .lock()
+            .values()
+            .filter(|weak| weak.strong_count() > 0)
+            .count()
+    }
+}